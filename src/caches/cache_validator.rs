@@ -0,0 +1,179 @@
+use sha2::{Digest, Sha256};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// データセットの更新日時（UNIX時間、秒）を保持する環境変数
+pub const DATASET_UPDATED_AT_KEY: &str = "DATASET_UPDATED_AT";
+
+/// キャッシュの最大有効期間（秒）を上書きする環境変数の接頭辞
+pub const CACHE_MAX_AGE_KEY_PREFIX: &str = "CACHE_MAX_AGE_";
+
+/// レスポンスのキャッシュ検証情報
+#[derive(Debug, PartialEq, Clone)]
+pub struct CacheValidator {
+    pub etag: String,
+    pub last_modified: Option<SystemTime>,
+}
+
+impl CacheValidator {
+    /// Content-Type と送信するボディのバイト列から強い ETag を算出する
+    ///
+    /// 圧縮後のボディから算出するため、圧縮形式ごとに異なる ETag になる。
+    pub fn new(content_type: &str, body: &[u8], last_modified: Option<SystemTime>) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(content_type.as_bytes());
+        hasher.update([0]);
+        hasher.update(body);
+        let digest = hasher.finalize();
+
+        let hash: String = digest[..16]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        Self {
+            etag: format!(r#""{}""#, hash),
+            last_modified,
+        }
+    }
+
+    /// If-None-Match、If-Modified-Since の順に検証し、304 を返すべきか判定する
+    ///
+    /// If-None-Match がある場合は If-Modified-Since を無視する（RFC 9110 13.2.2）。
+    pub fn is_not_modified(
+        &self,
+        if_none_match: Option<&str>,
+        if_modified_since: Option<&str>,
+    ) -> bool {
+        if let Some(if_none_match) = if_none_match {
+            return if_none_match.split(',').any(|tag| {
+                let tag = tag.trim();
+                // If-None-Match は弱い比較で判定する
+                tag == "*" || tag.trim_start_matches("W/") == self.etag
+            });
+        }
+
+        match (if_modified_since, self.last_modified) {
+            (Some(if_modified_since), Some(last_modified)) => {
+                match httpdate::parse_http_date(if_modified_since) {
+                    Ok(since) => truncate_to_seconds(last_modified) <= since,
+                    Err(_) => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    pub fn last_modified_value(&self) -> Option<String> {
+        self.last_modified
+            .map(|last_modified| httpdate::fmt_http_date(truncate_to_seconds(last_modified)))
+    }
+}
+
+/// 環境変数からデータセットの更新日時を取得する（未設定または0の場合は None）
+pub fn dataset_updated_at() -> Option<SystemTime> {
+    let seconds = std::env::var(DATASET_UPDATED_AT_KEY)
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|seconds| *seconds > 0)?;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+/// Cache-Control の max-age を取得する
+///
+/// `CACHE_MAX_AGE_<route_key>` が設定されている場合はその値を優先する。
+pub fn cache_max_age(route_key: &str, default_max_age: u32) -> u32 {
+    std::env::var(format!("{}{}", CACHE_MAX_AGE_KEY_PREFIX, route_key))
+        .ok()
+        .and_then(|max_age| max_age.trim().parse::<u32>().ok())
+        .unwrap_or(default_max_age)
+}
+
+/// HTTP-date は秒単位のため、比較する前に秒未満を切り捨てる
+fn truncate_to_seconds(time: SystemTime) -> SystemTime {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => UNIX_EPOCH + Duration::from_secs(duration.as_secs()),
+        Err(_) => time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sun, 06 Nov 1994 08:49:37 GMT
+    fn last_modified() -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(784_111_777_500)
+    }
+
+    fn validator() -> CacheValidator {
+        CacheValidator::new("application/json", b"{}", Some(last_modified()))
+    }
+
+    #[test]
+    fn etag_is_strong_and_depends_on_content_type() {
+        let etag = validator().etag;
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag.len(), 34);
+        assert_eq!(etag, validator().etag);
+        assert_ne!(
+            etag,
+            CacheValidator::new("application/cbor", b"{}", None).etag
+        );
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let validator = validator();
+        let weak = format!("W/{}", validator.etag);
+        assert!(validator.is_not_modified(Some(&validator.etag), None));
+        assert!(validator.is_not_modified(Some(&weak), None));
+        assert!(!validator.is_not_modified(Some(r#""other""#), None));
+        assert!(!validator.is_not_modified(Some(r#"W/"other""#), None));
+    }
+
+    #[test]
+    fn if_none_match_accepts_wildcard_and_lists() {
+        let validator = validator();
+        assert!(validator.is_not_modified(Some("*"), None));
+        assert!(
+            validator.is_not_modified(Some(&format!(r#""a", W/"b",{} "#, validator.etag)), None)
+        );
+        assert!(!validator.is_not_modified(Some(r#""a", W/"b""#), None));
+    }
+
+    #[test]
+    fn if_none_match_takes_precedence_over_if_modified_since() {
+        let validator = validator();
+        let future = "Sun, 06 Nov 2044 08:49:37 GMT";
+        assert!(validator.is_not_modified(None, Some(future)));
+        assert!(!validator.is_not_modified(Some(r#""other""#), Some(future)));
+        assert!(
+            validator.is_not_modified(Some(&validator.etag), Some("Sat, 01 Jan 1994 00:00:00 GMT"))
+        );
+    }
+
+    #[test]
+    fn if_modified_since_parses_http_dates() {
+        let validator = validator();
+        // IMF-fixdate、RFC 850、asctime の各形式（秒未満は切り捨てて比較する）
+        assert!(validator.is_not_modified(None, Some("Sun, 06 Nov 1994 08:49:37 GMT")));
+        assert!(validator.is_not_modified(None, Some("Sunday, 06-Nov-94 08:49:37 GMT")));
+        assert!(validator.is_not_modified(None, Some("Sun Nov  6 08:49:37 1994")));
+        assert!(!validator.is_not_modified(None, Some("Sun, 06 Nov 1994 08:49:36 GMT")));
+        assert!(!validator.is_not_modified(None, Some("2044-11-06T08:49:37Z")));
+        assert!(!validator.is_not_modified(None, None));
+
+        let unknown = CacheValidator::new("application/json", b"{}", None);
+        assert!(!unknown.is_not_modified(None, Some("Sun, 06 Nov 2044 08:49:37 GMT")));
+    }
+
+    #[test]
+    fn last_modified_value_is_http_date() {
+        assert_eq!(
+            validator().last_modified_value(),
+            Some("Sun, 06 Nov 1994 08:49:37 GMT".to_string())
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod cache_validator;
+
+pub use cache_validator::*;
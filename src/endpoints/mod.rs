@@ -0,0 +1,5 @@
+mod response_body;
+mod response_type;
+
+pub use response_body::*;
+pub use response_type::*;
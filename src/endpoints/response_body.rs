@@ -0,0 +1,61 @@
+use crate::models::{Hut, Mountain, MountainRange, Route};
+use crate::services::{NearbyHut, ParkCount};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// API の情報
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiInfoResponse {
+    pub about: String,
+    pub mountains: &'static str,
+    pub documents: &'static str,
+}
+
+/// 子の山岳一覧
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubPeakListResponse {
+    pub mountains: Vec<Mountain>,
+    pub total: usize,
+}
+
+/// 山脈・山地の一覧
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MountainRangeListResponse {
+    pub ranges: Vec<MountainRange>,
+    pub total: usize,
+}
+
+/// 国立公園の一覧
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ParkListResponse {
+    pub parks: Vec<ParkCount>,
+    pub total: usize,
+}
+
+/// 登山ルートの一覧
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteListResponse {
+    pub routes: Vec<Route>,
+    pub total: usize,
+}
+
+/// 山小屋の一覧
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HutListResponse {
+    pub huts: Vec<Hut>,
+    pub total: usize,
+}
+
+/// 指定地点の周辺にある山小屋の一覧（近い順）
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NearbyHutListResponse {
+    pub huts: Vec<NearbyHut>,
+    pub total: usize,
+}
@@ -0,0 +1,172 @@
+use crate::caches::cache_max_age;
+use crate::serializers::{BodyFormat, DATA_FORMATS};
+
+/// エンドポイントごとのレスポンスの種類
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ResponseType {
+    ApiInfo,
+    Mountain,
+    MountainList,
+    NearbyMountainList,
+    SubPeakList,
+    CompareMountains,
+    MountainRangeList,
+    MountainRangeMountainList,
+    ParkList,
+    RouteList,
+    Route,
+    RouteTrack,
+    HutList,
+    Hut,
+    MountainHutList,
+    OpenApi,
+    Error,
+}
+
+/// API のパスとレスポンスの種類（`{id}` は数値のIDに一致する）
+///
+/// template.yaml の Events、OpenAPI ドキュメントの paths と一致させる。
+pub const ENDPOINTS: [(&str, ResponseType); 16] = [
+    ("/", ResponseType::ApiInfo),
+    ("/openapi.json", ResponseType::OpenApi),
+    ("/mountains", ResponseType::MountainList),
+    ("/mountains/compare", ResponseType::CompareMountains),
+    ("/mountains/{id}", ResponseType::Mountain),
+    ("/mountains/{id}/peaks", ResponseType::SubPeakList),
+    ("/mountains/{id}/routes", ResponseType::RouteList),
+    ("/mountains/{id}/huts", ResponseType::MountainHutList),
+    ("/mountains/{id}/nearby", ResponseType::NearbyMountainList),
+    ("/ranges", ResponseType::MountainRangeList),
+    (
+        "/ranges/{id}/mountains",
+        ResponseType::MountainRangeMountainList,
+    ),
+    ("/parks", ResponseType::ParkList),
+    ("/routes/{id}", ResponseType::Route),
+    ("/routes/{id}/track", ResponseType::RouteTrack),
+    ("/huts", ResponseType::HutList),
+    ("/huts/{id}", ResponseType::Hut),
+];
+
+impl ResponseType {
+    /// Cache-Control の max-age（秒）
+    ///
+    /// 環境変数 `CACHE_MAX_AGE_<ルート名>` で上書きできる。
+    pub fn cache_max_age(&self) -> u32 {
+        let (route_key, default_max_age) = match self {
+            ResponseType::ApiInfo => ("API_INFO", 86400),
+            ResponseType::Mountain => ("MOUNTAIN", 3600),
+            ResponseType::MountainList => ("MOUNTAIN_LIST", 3600),
+            ResponseType::NearbyMountainList => ("NEARBY_MOUNTAIN_LIST", 3600),
+            ResponseType::SubPeakList => ("SUB_PEAK_LIST", 3600),
+            ResponseType::CompareMountains => ("COMPARE_MOUNTAINS", 3600),
+            ResponseType::MountainRangeList => ("MOUNTAIN_RANGE_LIST", 86400),
+            ResponseType::MountainRangeMountainList => ("MOUNTAIN_RANGE_MOUNTAIN_LIST", 3600),
+            ResponseType::ParkList => ("PARK_LIST", 3600),
+            ResponseType::RouteList => ("ROUTE_LIST", 3600),
+            ResponseType::Route => ("ROUTE", 3600),
+            ResponseType::RouteTrack => ("ROUTE_TRACK", 3600),
+            ResponseType::HutList => ("HUT_LIST", 3600),
+            ResponseType::Hut => ("HUT", 3600),
+            ResponseType::MountainHutList => ("MOUNTAIN_HUT_LIST", 3600),
+            ResponseType::OpenApi => ("OPENAPI", 86400),
+            ResponseType::Error => ("ERROR", 0),
+        };
+        cache_max_age(route_key, default_max_age)
+    }
+
+    /// エンドポイントごとに選択できるレスポンスの形式（先頭が既定の形式）
+    pub fn allowed_formats(&self) -> &'static [BodyFormat] {
+        match self {
+            ResponseType::Mountain => &[
+                BodyFormat::Json { pretty: true },
+                BodyFormat::GeoJson,
+                BodyFormat::Gpx,
+                BodyFormat::Kml,
+                BodyFormat::MessagePack,
+                BodyFormat::Cbor,
+            ],
+            ResponseType::MountainList => &[
+                BodyFormat::Json { pretty: true },
+                BodyFormat::GeoJson,
+                BodyFormat::Csv,
+                BodyFormat::Gpx,
+                BodyFormat::Kml,
+                BodyFormat::MessagePack,
+                BodyFormat::Cbor,
+            ],
+            ResponseType::RouteTrack => &[
+                BodyFormat::GeoJson,
+                BodyFormat::Json { pretty: true },
+                BodyFormat::MessagePack,
+                BodyFormat::Cbor,
+            ],
+            ResponseType::OpenApi => &[BodyFormat::Json { pretty: true }],
+            _ => &DATA_FORMATS,
+        }
+    }
+}
+
+/// リクエストのパスからレスポンスの種類を判定する
+pub fn response_type(uri_path: &str) -> ResponseType {
+    let path = uri_path.replace("/api/v1", "");
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    ENDPOINTS
+        .iter()
+        .find(|(endpoint, _)| {
+            let endpoint_segments: Vec<&str> =
+                endpoint.split('/').filter(|s| !s.is_empty()).collect();
+            endpoint_segments.len() == segments.len()
+                && endpoint_segments.iter().zip(&segments).all(
+                    |(expected, segment)| match *expected {
+                        "{id}" => segment.parse::<u32>().is_ok(),
+                        _ => expected == segment,
+                    },
+                )
+        })
+        .map(|(_, response_type)| *response_type)
+        .unwrap_or(ResponseType::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_every_endpoint() {
+        for (endpoint, expected) in ENDPOINTS {
+            let path = endpoint.replace("{id}", "3776");
+            assert_eq!(response_type(&path), expected, "{}", path);
+            assert_eq!(
+                response_type(&format!("/api/v1{}", path)),
+                expected,
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_segments_take_precedence_over_id() {
+        assert_eq!(
+            response_type("/mountains/compare"),
+            ResponseType::CompareMountains
+        );
+        assert_eq!(response_type("/mountains/1/"), ResponseType::Mountain);
+        assert_eq!(response_type("/huts/"), ResponseType::HutList);
+    }
+
+    #[test]
+    fn unknown_paths_are_errors() {
+        for path in [
+            "/mountains/abc",
+            "/mountains/-1",
+            "/mountains/1/unknown",
+            "/routes/1/track/1",
+            "/unknown",
+        ] {
+            assert_eq!(response_type(path), ResponseType::Error, "{}", path);
+        }
+    }
+}
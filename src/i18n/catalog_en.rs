@@ -0,0 +1,56 @@
+use crate::i18n::Message;
+use crate::models::MAX_ALERT_LEVEL;
+
+pub(crate) fn text(message: &Message) -> String {
+    match message {
+        Message::ApiAbout => "An API that provides major mountains in Japan in JSON.".to_string(),
+        Message::InternalError => "An error occurred.".to_string(),
+        Message::NotAcceptable => "The requested format is not supported.".to_string(),
+        Message::PathNotFound => "The requested path does not exist.".to_string(),
+        Message::InvalidParameterTitle => "Invalid parameter.".to_string(),
+        Message::InvalidParameterDetail => {
+            "One or more query parameters are invalid. See errors for details.".to_string()
+        }
+        Message::NotFoundTitle => "Resource not found.".to_string(),
+        Message::NotAcceptableTitle => "Not acceptable.".to_string(),
+        Message::InternalErrorTitle => "Internal server error.".to_string(),
+        Message::MountainNotFound => "The mountain was not found.".to_string(),
+        Message::MountainRangeNotFound => "The mountain range was not found.".to_string(),
+        Message::RouteNotFound => "The route was not found.".to_string(),
+        Message::TrackNotFound => "The route track was not found.".to_string(),
+        Message::HutNotFound => "The mountain hut was not found.".to_string(),
+        Message::InvalidPretty => "pretty must be true or false.".to_string(),
+        Message::InvalidLang => "lang must be ja or en.".to_string(),
+        Message::InvalidStrict => "strict must be true or false.".to_string(),
+        Message::UnknownParameter => "Unknown parameter.".to_string(),
+        Message::InvalidPrefectureId => "Invalid prefecture ID.".to_string(),
+        Message::InvalidTagId => "Invalid tag ID.".to_string(),
+        Message::InvalidMountainRangeId => "Invalid mountain range ID.".to_string(),
+        Message::InvalidParkId => "Invalid national park ID.".to_string(),
+        Message::InvalidTopLevelOnly => "top_level_only must be true or false.".to_string(),
+        Message::InvalidVolcano => "volcano must be true or false.".to_string(),
+        Message::InvalidAlertLevelMin => format!(
+            "alert_level_min must be an integer from 1 to {}.",
+            MAX_ALERT_LEVEL
+        ),
+        Message::InvalidInSeason => "in_season must be an integer from 1 to 12.".to_string(),
+        Message::InvalidDifficultyMax => {
+            "difficulty_max must be a technical grade from A to E, optionally followed by a physical grade from 1 to 10 (e.g. C, C5).".to_string()
+        }
+        Message::InvalidOffset => "offset must be an integer of 0 or more.".to_string(),
+        Message::OffsetOutOfRange => "offset must not exceed the number of results.".to_string(),
+        Message::InvalidLimit => "limit must be an integer of 1 or more.".to_string(),
+        Message::InvalidSort => "Invalid sort.".to_string(),
+        Message::InvalidRadius => "radius must be a number (km) greater than 0.".to_string(),
+        Message::InvalidIds => "ids must be 2 to 3 comma-separated mountain IDs.".to_string(),
+        Message::InvalidMountainId => "Invalid mountain ID.".to_string(),
+        Message::DuplicatedMountainId => "Mountain IDs are duplicated.".to_string(),
+        Message::InvalidLatitude => "latitude must be a number from -90 to 90.".to_string(),
+        Message::InvalidLongitude => "longitude must be a number from -180 to 180.".to_string(),
+        Message::InvalidSimplify => "simplify must be a number (m) greater than 0.".to_string(),
+        Message::WaypointKana => "Kana".to_string(),
+        Message::WaypointPrefectures => "Prefectures".to_string(),
+        Message::WaypointTags => "Tags".to_string(),
+        Message::WaypointSeparator => ", ".to_string(),
+    }
+}
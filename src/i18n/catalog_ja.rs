@@ -0,0 +1,60 @@
+use crate::i18n::Message;
+use crate::models::MAX_ALERT_LEVEL;
+
+pub(crate) fn text(message: &Message) -> String {
+    match message {
+        Message::ApiAbout => "日本の主な山岳をJSON形式で提供するAPIです。".to_string(),
+        Message::InternalError => "エラーが発生しました。".to_string(),
+        Message::NotAcceptable => "対応していない形式が指定されました。".to_string(),
+        Message::PathNotFound => "指定されたパスは存在しません。".to_string(),
+        Message::InvalidParameterTitle => "不正なパラメータです。".to_string(),
+        Message::InvalidParameterDetail => {
+            "クエリパラメータが不正です。詳細はerrorsを確認してください。".to_string()
+        }
+        Message::NotFoundTitle => "リソースが見つかりません。".to_string(),
+        Message::NotAcceptableTitle => "対応していない形式です。".to_string(),
+        Message::InternalErrorTitle => "サーバーエラーです。".to_string(),
+        Message::MountainNotFound => "山岳情報が見つかりませんでした。".to_string(),
+        Message::MountainRangeNotFound => "山脈情報が見つかりませんでした。".to_string(),
+        Message::RouteNotFound => "ルート情報が見つかりませんでした。".to_string(),
+        Message::TrackNotFound => "ルートの軌跡が見つかりませんでした。".to_string(),
+        Message::HutNotFound => "山小屋情報が見つかりませんでした。".to_string(),
+        Message::InvalidPretty => "prettyにはtrueまたはfalseを指定してください。".to_string(),
+        Message::InvalidLang => "langにはjaまたはenを指定してください。".to_string(),
+        Message::InvalidStrict => "strictにはtrueまたはfalseを指定してください。".to_string(),
+        Message::UnknownParameter => "対応していないパラメータです。".to_string(),
+        Message::InvalidPrefectureId => "不正な都道府県IDです。".to_string(),
+        Message::InvalidTagId => "不正なタグIDです。".to_string(),
+        Message::InvalidMountainRangeId => "不正な山脈IDです。".to_string(),
+        Message::InvalidParkId => "不正な国立公園IDです。".to_string(),
+        Message::InvalidTopLevelOnly => {
+            "top_level_onlyにはtrueまたはfalseを指定してください。".to_string()
+        }
+        Message::InvalidVolcano => "volcanoにはtrueまたはfalseを指定してください。".to_string(),
+        Message::InvalidAlertLevelMin => format!(
+            "alert_level_minは1から{}の整数を指定してください。",
+            MAX_ALERT_LEVEL
+        ),
+        Message::InvalidInSeason => "in_seasonは1から12の整数を指定してください。".to_string(),
+        Message::InvalidDifficultyMax => {
+            "difficulty_maxにはAからEの技術的難易度、または1から10の体力度を続けた値（例: C、C5）を指定してください。".to_string()
+        }
+        Message::InvalidOffset => "offsetは0以上の整数を指定してください。".to_string(),
+        Message::OffsetOutOfRange => "offsetは検索結果の件数以下を指定してください。".to_string(),
+        Message::InvalidLimit => "limitは1以上の整数を指定してください。".to_string(),
+        Message::InvalidSort => "不正なソート指定です。".to_string(),
+        Message::InvalidRadius => "radiusは0より大きい数値(km)を指定してください。".to_string(),
+        Message::InvalidIds => {
+            "idsには2件から3件の山岳IDをカンマ区切りで指定してください。".to_string()
+        }
+        Message::InvalidMountainId => "不正な山岳IDです。".to_string(),
+        Message::DuplicatedMountainId => "山岳IDが重複しています。".to_string(),
+        Message::InvalidLatitude => "latitudeは-90から90の数値を指定してください。".to_string(),
+        Message::InvalidLongitude => "longitudeは-180から180の数値を指定してください。".to_string(),
+        Message::InvalidSimplify => "simplifyは0より大きい数値(m)を指定してください。".to_string(),
+        Message::WaypointKana => "よみ".to_string(),
+        Message::WaypointPrefectures => "都道府県".to_string(),
+        Message::WaypointTags => "タグ".to_string(),
+        Message::WaypointSeparator => "、".to_string(),
+    }
+}
@@ -0,0 +1,140 @@
+/// レスポンスの言語
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Language {
+    Ja,
+    En,
+}
+
+impl Language {
+    /// Content-Language ヘッダーの値
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Language::Ja => "ja",
+            Language::En => "en",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        // 地域などのサブタグは無視する（en-US は en とみなす）
+        let primary = tag.split('-').next().unwrap_or("").trim().to_lowercase();
+        match primary.as_str() {
+            "ja" => Some(Language::Ja),
+            "en" => Some(Language::En),
+            _ => None,
+        }
+    }
+}
+
+/// lang パラメータ、Accept-Language ヘッダーの順に言語を選択する
+///
+/// lang パラメータが対応していない言語の場合はエラーとする。
+/// Accept-Language に対応する言語がない場合は日本語とする。
+#[allow(clippy::result_unit_err)]
+pub fn negotiate_language(
+    lang_param: Option<&str>,
+    accept_language: Option<&str>,
+) -> Result<Language, ()> {
+    if let Some(lang) = lang_param {
+        return Language::from_tag(lang).ok_or(());
+    }
+
+    let accept_language = match accept_language {
+        Some(accept_language) => accept_language,
+        None => return Ok(Language::Ja),
+    };
+
+    let mut languages: Vec<(Language, f32)> = Vec::new();
+    for language_range in accept_language.split(',') {
+        let mut parts = language_range.split(';');
+        let tag = parts.next().unwrap_or("");
+        let mut quality = 1.0;
+        for param in parts {
+            if let Some(q) = param.trim().strip_prefix("q=") {
+                quality = q.trim().parse::<f32>().unwrap_or(0.0);
+            }
+        }
+        if let Some(language) = Language::from_tag(tag) {
+            if quality > 0.0 {
+                languages.push((language, quality));
+            }
+        }
+    }
+    // 同じ q 値の場合は記述順を優先する
+    languages.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(languages
+        .first()
+        .map(|(language, _)| *language)
+        .unwrap_or(Language::Ja))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lang_param_takes_precedence() {
+        assert_eq!(negotiate_language(Some("en"), Some("ja")), Ok(Language::En));
+        assert_eq!(negotiate_language(Some("JA"), Some("en")), Ok(Language::Ja));
+        assert_eq!(negotiate_language(Some("en-GB"), None), Ok(Language::En));
+        assert_eq!(negotiate_language(Some("fr"), Some("en")), Err(()));
+    }
+
+    #[test]
+    fn accept_language_is_ordered_by_quality() {
+        assert_eq!(
+            negotiate_language(None, Some("ja;q=0.5, en;q=0.8")),
+            Ok(Language::En)
+        );
+        assert_eq!(
+            negotiate_language(None, Some("fr, en;q=0.3, ja;q=0.7")),
+            Ok(Language::Ja)
+        );
+        // 同じ q 値の場合は記述順
+        assert_eq!(negotiate_language(None, Some("en, ja")), Ok(Language::En));
+        assert_eq!(negotiate_language(None, Some("ja, en")), Ok(Language::Ja));
+    }
+
+    #[test]
+    fn zero_quality_excludes_language() {
+        assert_eq!(
+            negotiate_language(None, Some("ja;q=0, en;q=0.1")),
+            Ok(Language::En)
+        );
+        assert_eq!(
+            negotiate_language(None, Some("en;q=0, ja;q=0.1")),
+            Ok(Language::Ja)
+        );
+        // 不正な q 値は 0 とみなす
+        assert_eq!(negotiate_language(None, Some("en;q=abc")), Ok(Language::Ja));
+    }
+
+    #[test]
+    fn region_subtags_are_ignored() {
+        assert_eq!(
+            negotiate_language(None, Some("en-US,en;q=0.9")),
+            Ok(Language::En)
+        );
+        assert_eq!(negotiate_language(None, Some("ja-JP")), Ok(Language::Ja));
+        assert_eq!(Language::En.tag(), "en");
+    }
+
+    #[test]
+    fn falls_back_to_japanese() {
+        assert_eq!(negotiate_language(None, None), Ok(Language::Ja));
+        assert_eq!(negotiate_language(None, Some("")), Ok(Language::Ja));
+        assert_eq!(
+            negotiate_language(None, Some("fr, de;q=0.5")),
+            Ok(Language::Ja)
+        );
+        assert_eq!(negotiate_language(None, Some("*")), Ok(Language::Ja));
+    }
+
+    #[test]
+    fn invalid_prefecture_id_message_ends_with_full_stop() {
+        assert_eq!(
+            crate::i18n::Message::InvalidPrefectureId.text(&Language::Ja),
+            "不正な都道府県IDです。"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use crate::i18n::{catalog_en, catalog_ja, Language};
+
+/// レスポンスに含めるメッセージ
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Message {
+    ApiAbout,
+    InternalError,
+    NotAcceptable,
+    PathNotFound,
+    InvalidParameterTitle,
+    InvalidParameterDetail,
+    NotFoundTitle,
+    NotAcceptableTitle,
+    InternalErrorTitle,
+    MountainNotFound,
+    MountainRangeNotFound,
+    RouteNotFound,
+    TrackNotFound,
+    HutNotFound,
+    InvalidPretty,
+    InvalidLang,
+    InvalidStrict,
+    UnknownParameter,
+    InvalidPrefectureId,
+    InvalidTagId,
+    InvalidMountainRangeId,
+    InvalidParkId,
+    InvalidTopLevelOnly,
+    InvalidVolcano,
+    InvalidAlertLevelMin,
+    InvalidInSeason,
+    InvalidDifficultyMax,
+    InvalidOffset,
+    OffsetOutOfRange,
+    InvalidLimit,
+    InvalidSort,
+    InvalidRadius,
+    InvalidIds,
+    InvalidMountainId,
+    DuplicatedMountainId,
+    InvalidLatitude,
+    InvalidLongitude,
+    InvalidSimplify,
+    WaypointKana,
+    WaypointPrefectures,
+    WaypointTags,
+    WaypointSeparator,
+}
+
+impl Message {
+    /// 指定した言語のメッセージカタログから文言を取得する
+    pub fn text(&self, language: &Language) -> String {
+        match language {
+            Language::Ja => catalog_ja::text(self),
+            Language::En => catalog_en::text(self),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod catalog_en;
+mod catalog_ja;
+mod language;
+mod message;
+
+pub use language::*;
+pub use message::*;
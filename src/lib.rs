@@ -1,3 +1,9 @@
+pub mod caches;
+pub mod endpoints;
+pub mod i18n;
 pub mod models;
+pub mod openapi;
+pub mod queries;
 mod repositories;
+pub mod serializers;
 pub mod services;
@@ -1,133 +1,405 @@
 use aws_sdk_dynamodb::Client;
 use lambda_http::{
     handler,
+    http::HeaderMap,
     lambda_runtime::{self, Context},
-    IntoResponse, Request, RequestExt, Response, StrMap,
+    Body, IntoResponse, Request, RequestExt, Response, StrMap,
 };
+use mountix_serverless::caches::{dataset_updated_at, CacheValidator};
+use mountix_serverless::endpoints::{
+    response_type, ApiInfoResponse, HutListResponse, MountainRangeListResponse,
+    NearbyHutListResponse, ParkListResponse, ResponseType, RouteListResponse, SubPeakListResponse,
+};
+use mountix_serverless::i18n::{negotiate_language, Language, Message};
 use mountix_serverless::models::{
-    PrefectureBaseMapper, PrefectureMapper, TagBaseMapper, TagMapper,
+    localize_prefectures, localize_tags, to_mountain_feature, to_mountains_csv, to_mountains_gpx,
+    to_mountains_kml, FeatureCollection, Mountain, MountainListResponse, MountainRangeBaseMapper,
+    MountainRangeMapper, ParameterError, Problem, ProblemType,
+};
+use mountix_serverless::openapi::openapi_document;
+use mountix_serverless::queries::MountainQuery;
+use mountix_serverless::serializers::{
+    encode_body, negotiate_format, serialize_body, BodyFormat, NegotiationError, SerializedBody,
 };
 use mountix_serverless::services;
-use mountix_serverless::services::{RangeCondition, SearchCondition, SearchType};
-use serde::{Deserialize, Serialize};
+use mountix_serverless::services::{
+    compare_ids, NearbyCondition, NearbyHutCondition, RangeCondition, SearchCondition, SearchType,
+    ServiceError,
+};
+use serde::Serialize;
 
 type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
-enum ResponseType {
-    ApiInfo,
-    Mountain,
-    MountainList,
-    Error,
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     lambda_runtime::run(handler(get_response)).await?;
     Ok(())
 }
 
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ErrResponse {
-    messages: Vec<String>,
-}
-
 async fn get_response(event: Request, _: Context) -> Result<impl IntoResponse, Error> {
     let config = aws_config::load_from_env().await;
     let client = Client::new(&config);
 
     let uri_path = event.uri().path();
     let query_params = event.query_string_parameters();
+    let headers = event.headers();
+    let header_value = |key: &str| headers.get(key).and_then(|value| value.to_str().ok());
 
     let mut mountain_id = "".to_string();
     if let Some(id) = event.path_parameters().get("id") {
         mountain_id = id.to_string();
     }
 
-    let mut json = format!(
-        r#"{{"about": "{}", "mountains": "{}", "documents": "{}"}}"#,
-        "日本の主な山岳をJSON形式で提供するAPIです。",
-        "https://mountix.codemountains.org/api/v1/mountains",
-        "https://mountix-docs.codemountains.org/"
-    );
-    let mut status = 200;
-    match response_type(&uri_path.to_string(), &mountain_id) {
-        ResponseType::ApiInfo => {}
-        ResponseType::Mountain => match get_mountain(&client, &mountain_id).await {
-            Ok(result) => {
-                json = result;
-            }
+    let language =
+        match negotiate_language(query_params.get("lang"), header_value("Accept-Language")) {
+            Ok(language) => language,
             Err(_) => {
-                status = 404;
-                json = format!(r#"{{"message": "{}"}}"#, "山岳情報が見つかりませんでした。");
+                let errors = vec![ParameterError::new("lang", Message::InvalidLang)];
+                let problem = Problem::invalid_parameters(&errors, &Language::Ja);
+                return Ok(problem_response(problem, uri_path, headers, &Language::Ja));
             }
-        },
-        ResponseType::MountainList => match search_mountains(&client, &query_params).await {
-            Ok(result) => {
-                let mut limit = "null".to_string();
-                if let Some(l) = result.limit {
-                    limit = l.to_string();
-                }
+        };
+
+    let response_type = response_type(uri_path);
+    let format = match negotiate_format(
+        query_params.get("format"),
+        header_value("Accept"),
+        query_params.get("pretty"),
+        response_type.allowed_formats(),
+    ) {
+        Ok(format) => format,
+        Err(NegotiationError::NotAcceptable) => {
+            let problem = Problem::new(
+                ProblemType::NotAcceptable,
+                Message::NotAcceptable,
+                &language,
+            );
+            return Ok(problem_response(problem, uri_path, headers, &language));
+        }
+        Err(NegotiationError::InvalidPretty) => {
+            let errors = vec![ParameterError::new("pretty", Message::InvalidPretty)];
+            let problem = Problem::invalid_parameters(&errors, &language);
+            return Ok(problem_response(problem, uri_path, headers, &language));
+        }
+    };
+
+    let not_found = |message: Message| Problem::new(ProblemType::NotFound, message, &language);
+    let service_problem =
+        |error: ServiceError, message: Message| service_problem(error, message, &language);
+    let invalid_parameters =
+        |errors: Vec<ParameterError>| Problem::invalid_parameters(&errors, &language);
 
-                json = format!(
-                    r#"{{"mountains": {}, "total": {}, "offset": {}, "limit": {}}}"#,
-                    result.mountains_json, result.total, result.offset, limit
-                );
+    let result = match response_type {
+        ResponseType::ApiInfo => {
+            get_api_info(&format, &language).map_err(|_| Problem::internal_error(&language))
+        }
+        ResponseType::Mountain => {
+            get_mountain(&client, &mountain_id, &query_params, &format, &language)
+                .await
+                .map_err(|error| service_problem(error, Message::MountainNotFound))
+        }
+        ResponseType::MountainList => {
+            match search_mountains(&client, &query_params, Vec::new(), &format, &language).await {
+                Ok(result) => searched_result_body(result, &format, &query_params, &language)
+                    .map_err(|_| Problem::internal_error(&language)),
+                Err(problem) => Err(problem),
             }
-            Err(e) => {
-                status = 400;
-                let err_messages = ErrResponse { messages: e };
-                match serde_json::to_string_pretty(&err_messages) {
-                    Ok(msg) => json = msg,
-                    Err(_) => {
-                        json = format!(
-                            r#"{{"message": "{}"}}"#,
-                            "エラーが発生しました。".to_string()
-                        )
-                    }
+        }
+        ResponseType::ParkList => get_parks(&client, &format)
+            .await
+            .map_err(|error| service_problem(error, Message::PathNotFound)),
+        ResponseType::MountainRangeList => {
+            get_mountain_ranges(&format).map_err(|_| Problem::internal_error(&language))
+        }
+        ResponseType::MountainRangeMountainList => match range_condition(&mountain_id) {
+            Some(condition) => {
+                match search_mountains(&client, &query_params, vec![condition], &format, &language)
+                    .await
+                {
+                    Ok(result) => searched_result_body(result, &format, &query_params, &language)
+                        .map_err(|_| Problem::internal_error(&language)),
+                    Err(problem) => Err(problem),
                 }
             }
+            None => Err(not_found(Message::MountainRangeNotFound)),
+        },
+        ResponseType::SubPeakList => get_sub_peaks(&client, &mountain_id, &format, &language)
+            .await
+            .map_err(|error| service_problem(error, Message::MountainNotFound)),
+        ResponseType::RouteList => get_routes(&client, &mountain_id, &format)
+            .await
+            .map_err(|error| service_problem(error, Message::MountainNotFound)),
+        ResponseType::Route => get_route(&client, &mountain_id, &format)
+            .await
+            .map_err(|error| service_problem(error, Message::RouteNotFound)),
+        ResponseType::RouteTrack => match simplify_tolerance(&query_params) {
+            Ok(simplify) => match get_route_track(&client, &mountain_id, simplify, &format).await {
+                Ok(Some(body)) => Ok(body),
+                Ok(None) => Err(not_found(Message::TrackNotFound)),
+                Err(error) => Err(service_problem(error, Message::RouteNotFound)),
+            },
+            Err(errors) => Err(invalid_parameters(errors)),
         },
-        ResponseType::Error => {
-            status = 400;
-            json = format!(r#"{{"message": "{}"}}"#, uri_path.to_string());
+        ResponseType::HutList => match nearby_hut_condition(&query_params) {
+            Ok(condition) => get_huts(&client, condition, &format)
+                .await
+                .map_err(|error| service_problem(error, Message::PathNotFound)),
+            Err(errors) => Err(invalid_parameters(errors)),
+        },
+        ResponseType::Hut => get_hut(&client, &mountain_id, &format)
+            .await
+            .map_err(|error| service_problem(error, Message::HutNotFound)),
+        ResponseType::MountainHutList => get_mountain_huts(&client, &mountain_id, &format)
+            .await
+            .map_err(|error| service_problem(error, Message::MountainNotFound)),
+        ResponseType::CompareMountains => match compare_ids(&query_params) {
+            Ok(ids) => get_compared_mountains(&client, ids, &format, &language)
+                .await
+                .map_err(|error| service_problem(error, Message::MountainNotFound)),
+            Err(errors) => Err(invalid_parameters(errors)),
+        },
+        ResponseType::NearbyMountainList => match nearby_condition(&query_params) {
+            Ok(condition) => {
+                get_nearby_mountains(&client, &mountain_id, condition, &format, &language)
+                    .await
+                    .map_err(|error| service_problem(error, Message::MountainNotFound))
+            }
+            Err(errors) => Err(invalid_parameters(errors)),
+        },
+        ResponseType::OpenApi => serialize_body(&openapi_document(), &format)
+            .map_err(|_| Problem::internal_error(&language)),
+        ResponseType::Error => Err(not_found(Message::PathNotFound)),
+    };
+
+    match result {
+        Ok(body) => Ok(build_response(
+            200,
+            format.content_type(),
+            format.content_disposition(),
+            body,
+            headers,
+            Some(response_type.cache_max_age()),
+            &language,
+        )),
+        Err(problem) => Ok(problem_response(problem, uri_path, headers, &language)),
+    }
+}
+
+/// サービスのエラーを Problem に変換する（内部エラーの原因はここで出力する）
+fn service_problem(
+    error: ServiceError,
+    not_found_message: Message,
+    language: &Language,
+) -> Problem {
+    match error {
+        ServiceError::NotFound => Problem::new(ProblemType::NotFound, not_found_message, language),
+        ServiceError::OffsetOutOfRange => {
+            let errors = vec![ParameterError::new("offset", Message::OffsetOutOfRange)];
+            Problem::invalid_parameters(&errors, language)
+        }
+        ServiceError::Internal(cause) => {
+            eprintln!("{}", cause);
+            Problem::internal_error(language)
         }
     }
+}
 
-    // エラーのレスポンスをOkで実装する
-    Ok(Response::builder()
+/// シリアライズの失敗は内部エラーとする
+fn serialize_failed(_: ()) -> ServiceError {
+    ServiceError::Internal("レスポンスのシリアライズに失敗しました。".to_string())
+}
+
+/// エラーを application/problem+json で返す
+fn problem_response(
+    problem: Problem,
+    uri_path: &str,
+    headers: &HeaderMap,
+    language: &Language,
+) -> Response<Body> {
+    let problem = problem.with_instance(uri_path);
+
+    let status = problem.status;
+    let body = match serde_json::to_string_pretty(&problem) {
+        Ok(problem_json) => SerializedBody::Text(problem_json),
+        Err(_) => SerializedBody::Text("".to_string()),
+    };
+    build_response(
+        status,
+        "application/problem+json",
+        None,
+        body,
+        headers,
+        None,
+        language,
+    )
+}
+
+// エラーのレスポンスをOkで実装する
+fn build_response(
+    status: u16,
+    content_type: &str,
+    content_disposition: Option<&str>,
+    body: SerializedBody,
+    headers: &HeaderMap,
+    max_age: Option<u32>,
+    language: &Language,
+) -> Response<Body> {
+    let header_value = |key: &str| headers.get(key).and_then(|value| value.to_str().ok());
+
+    let mut builder = Response::builder()
         .status(status)
-        .header("Content-Type", "application/json")
+        .header("Content-Type", content_type)
+        .header("Content-Language", language.tag())
+        .header("Vary", "Accept, Accept-Encoding, Accept-Language")
         .header("Access-Control-Allow-Methods", "OPTIONS,GET")
         .header("Access-Control-Allow-Credential", "true")
-        .header("Access-Control-Allow-Origin", "*")
-        .body(json)
-        .expect("failed to render response"))
+        .header("Access-Control-Allow-Origin", "*");
+    if let Some(disposition) = content_disposition {
+        builder = builder.header("Content-Disposition", disposition);
+    }
+
+    // 圧縮したボディはバイナリとして返し、lambda_http が base64 にエンコードする
+    let (body, content_encoding) = encode_body(body, header_value("Accept-Encoding"));
+    if let Some(encoding) = content_encoding {
+        builder = builder.header("Content-Encoding", encoding.header_value());
+    }
+
+    // 成功したレスポンスのみキャッシュの対象とする
+    if let Some(max_age) = max_age {
+        let bytes = match &body {
+            SerializedBody::Text(text) => text.as_bytes(),
+            SerializedBody::Binary(bytes) => bytes.as_slice(),
+        };
+        let validator = CacheValidator::new(content_type, bytes, dataset_updated_at());
+
+        builder = builder
+            .header("ETag", validator.etag.as_str())
+            .header("Cache-Control", format!("public, max-age={}", max_age));
+        if let Some(last_modified) = validator.last_modified_value() {
+            builder = builder.header("Last-Modified", last_modified);
+        }
+
+        if validator.is_not_modified(
+            header_value("If-None-Match"),
+            header_value("If-Modified-Since"),
+        ) {
+            return builder
+                .status(304)
+                .body(Body::Empty)
+                .expect("failed to render response");
+        }
+    }
+
+    let body = match body {
+        SerializedBody::Text(text) => Body::Text(text),
+        SerializedBody::Binary(bytes) => Body::Binary(bytes),
+    };
+    builder.body(body).expect("failed to render response")
 }
 
-fn response_type(uri_path: &String, mountain_id: &String) -> ResponseType {
-    match uri_path.to_string().replace("/api/v1", "").as_str() {
-        "" | "/" => ResponseType::ApiInfo,
-        "/mountains" | "/mountains/" => ResponseType::MountainList,
-        _ => match mountain_id.to_string().parse::<u32>() {
-            Ok(_) => ResponseType::Mountain,
-            Err(_) => ResponseType::Error,
-        },
+fn get_api_info(format: &BodyFormat, language: &Language) -> Result<SerializedBody, ()> {
+    let api_info = ApiInfoResponse {
+        about: Message::ApiAbout.text(language),
+        mountains: "https://mountix.codemountains.org/api/v1/mountains",
+        documents: "https://mountix-docs.codemountains.org/",
+    };
+    serialize_body(&api_info, format)
+}
+
+fn searched_result_body(
+    mut result: SearchedResult,
+    format: &BodyFormat,
+    query_params: &StrMap,
+    language: &Language,
+) -> Result<SerializedBody, ()> {
+    for mountain in &mut result.mountains {
+        mountain.localize(language);
+    }
+
+    match format {
+        BodyFormat::GeoJson => {
+            let mut features = Vec::new();
+            for mountain in &result.mountains {
+                features.push(to_mountain_feature(mountain)?);
+            }
+
+            // total, offset, limit は foreign members として出力する
+            let feature_collection = FeatureCollection::new(features, &result);
+            serialize_body(&feature_collection, format)
+        }
+        BodyFormat::Csv => {
+            // bom=true の場合は Excel 向けに BOM を付与する
+            let with_bom = match query_params.get("bom") {
+                Some(bom) => bom.eq_ignore_ascii_case("true"),
+                None => false,
+            };
+            Ok(SerializedBody::Text(to_mountains_csv(
+                &result.mountains,
+                with_bom,
+            )))
+        }
+        BodyFormat::Gpx => Ok(SerializedBody::Text(to_mountains_gpx(
+            &result.mountains,
+            language,
+        ))),
+        BodyFormat::Kml => Ok(SerializedBody::Text(to_mountains_kml(
+            &result.mountains,
+            language,
+        ))),
+        BodyFormat::Json { .. } | BodyFormat::MessagePack | BodyFormat::Cbor => {
+            let mountain_list = MountainListResponse {
+                mountains: &result.mountains,
+                total: result.total,
+                offset: result.offset,
+                limit: result.limit,
+            };
+            serialize_body(&mountain_list, format)
+        }
     }
 }
 
-async fn get_mountain(client: &Client, id: &String) -> Result<String, ()> {
-    match services::get_mountain_by_id(&client, id.to_string()).await {
-        Ok(mountain) => match serde_json::to_string_pretty(&mountain) {
-            Ok(result) => Ok(result),
-            Err(_) => Err(()),
-        },
-        Err(_) => Err(()),
+async fn get_mountain(
+    client: &Client,
+    id: &String,
+    query_params: &StrMap,
+    format: &BodyFormat,
+    language: &Language,
+) -> Result<SerializedBody, ServiceError> {
+    let mut mountain = services::get_mountain_by_id(client, id.to_string()).await?;
+
+    // 標高順位: rank=true の場合のみ算出する
+    if let Some(rank) = query_params.get("rank") {
+        if rank.eq_ignore_ascii_case("true") {
+            mountain.rank = Some(services::get_mountain_rank(client, &mountain).await?);
+        }
+    }
+    mountain.localize(language);
+
+    match format {
+        BodyFormat::GeoJson => {
+            let feature = to_mountain_feature(&mountain).map_err(serialize_failed)?;
+            serialize_body(&feature, format).map_err(serialize_failed)
+        }
+        BodyFormat::Csv => Ok(SerializedBody::Text(to_mountains_csv(&[mountain], false))),
+        BodyFormat::Gpx => Ok(SerializedBody::Text(to_mountains_gpx(
+            &[mountain],
+            language,
+        ))),
+        BodyFormat::Kml => Ok(SerializedBody::Text(to_mountains_kml(
+            &[mountain],
+            language,
+        ))),
+        BodyFormat::Json { .. } | BodyFormat::MessagePack | BodyFormat::Cbor => {
+            serialize_body(&mountain, format).map_err(serialize_failed)
+        }
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 struct SearchedResult {
-    mountains_json: String,
+    #[serde(skip)]
+    mountains: Vec<Mountain>,
     total: usize,
     offset: usize,
     limit: Option<usize>,
@@ -136,99 +408,246 @@ struct SearchedResult {
 async fn search_mountains(
     client: &Client,
     query_params: &StrMap,
-) -> Result<SearchedResult, Vec<String>> {
-    let simple_err_message_list = vec!["エラーが発生しました。".to_string()];
-
-    let mut search_conditions: Vec<SearchCondition> = Vec::new();
-    let mut err_message_list: Vec<String> = Vec::new();
-
-    // 検索条件: 都道府県ID
-    if let Some(pref) = query_params.get("prefecture") {
-        if let Ok(pref_key) = pref.to_string().parse::<u32>() {
-            let pref_mapper = PrefectureMapper::new(pref_key);
-            match pref_mapper.to_prefecture() {
-                Ok(pref) => {
-                    search_conditions.push(SearchCondition {
-                        search_type: SearchType::Prefecture,
-                        value: pref,
-                    });
-                }
-                Err(_) => {
-                    err_message_list.push("不正な都道府県IDです".to_string());
-                }
+    mut search_conditions: Vec<SearchCondition>,
+    format: &BodyFormat,
+    language: &Language,
+) -> Result<SearchedResult, Problem> {
+    let query = match MountainQuery::parse(query_params) {
+        Ok(query) => query,
+        Err(errors) => return Err(Problem::invalid_parameters(&errors, language)),
+    };
+    search_conditions.extend(query.search_conditions());
+    let filter_condition = query.filter_condition();
+    // CSV はページングせず、絞り込んだ全件を出力する
+    let range_condition = match format {
+        BodyFormat::Csv => RangeCondition {
+            offset: 0,
+            limit: None,
+        },
+        _ => query.range_condition(),
+    };
+    let sort_key = query.sort;
+
+    // 検索条件が存在しない場合、scanを実行する
+    if search_conditions.is_empty() {
+        return match services::get_all_mountains(
+            client,
+            filter_condition,
+            range_condition,
+            &sort_key,
+        )
+        .await
+        {
+            Ok(searched_mountain_result) => Ok(SearchedResult {
+                mountains: searched_mountain_result.mountains,
+                total: searched_mountain_result.total,
+                offset: searched_mountain_result.offset,
+                limit: searched_mountain_result.limit,
+            }),
+            Err(error) => Err(service_problem(error, Message::MountainNotFound, language)),
+        };
+    }
+
+    match services::search_mountains(
+        client,
+        search_conditions,
+        filter_condition,
+        range_condition,
+        &sort_key,
+    )
+    .await
+    {
+        Ok(searched_mountain_result) => Ok(SearchedResult {
+            mountains: searched_mountain_result.mountains,
+            total: searched_mountain_result.total,
+            offset: searched_mountain_result.offset,
+            limit: searched_mountain_result.limit,
+        }),
+        Err(error) => Err(service_problem(error, Message::MountainNotFound, language)),
+    }
+}
+
+fn nearby_condition(query_params: &StrMap) -> Result<NearbyCondition, Vec<ParameterError>> {
+    let mut err_message_list: Vec<ParameterError> = Vec::new();
+
+    // radius 値チェック（km）
+    let mut radius_value = 20.0;
+    if let Some(radius) = query_params.get("radius") {
+        match radius.parse::<f64>() {
+            Ok(radius_temp) if radius_temp.is_finite() && radius_temp > 0.0 => {
+                radius_value = radius_temp;
+            }
+            _ => {
+                err_message_list.push(ParameterError::new("radius", Message::InvalidRadius));
             }
         }
     }
 
-    // 検索条件: タグ（百名山）
-    if let Some(tag) = query_params.get("tag") {
-        if let Ok(tag_key) = tag.to_string().parse::<u32>() {
-            let tag_mapper = TagMapper::new(tag_key);
-            match tag_mapper.to_tag() {
-                Ok(tag) => search_conditions.push(SearchCondition {
-                    search_type: SearchType::Tag,
-                    value: tag,
-                }),
-                Err(_) => {
-                    err_message_list.push("不正なタグIDです。".to_string());
-                }
+    // limit 値チェック
+    let mut limit_value = 10_usize;
+    if let Some(limit) = query_params.get("limit") {
+        match limit.parse::<usize>() {
+            Ok(limit_temp) if limit_temp > 0 => {
+                limit_value = limit_temp;
+            }
+            _ => {
+                err_message_list.push(ParameterError::new("limit", Message::InvalidLimit));
             }
         }
     }
 
-    // 検索条件: 山名
-    if let Some(mountain_name) = query_params.get("name") {
-        search_conditions.push(SearchCondition {
-            search_type: SearchType::Name,
-            value: mountain_name.to_string(),
-        });
+    if !err_message_list.is_empty() {
+        return Err(err_message_list);
+    }
+
+    Ok(NearbyCondition {
+        radius: radius_value,
+        limit: limit_value,
+    })
+}
+
+async fn get_nearby_mountains(
+    client: &Client,
+    id: &str,
+    nearby_condition: NearbyCondition,
+    format: &BodyFormat,
+    language: &Language,
+) -> Result<SerializedBody, ServiceError> {
+    let mut result =
+        services::get_nearby_mountains(client, id.to_string(), nearby_condition).await?;
+    for nearby_mountain in &mut result.mountains {
+        nearby_mountain.mountain.localize(language);
+    }
+    serialize_body(&result, format).map_err(serialize_failed)
+}
+
+async fn get_compared_mountains(
+    client: &Client,
+    ids: Vec<String>,
+    format: &BodyFormat,
+    language: &Language,
+) -> Result<SerializedBody, ServiceError> {
+    let mut result = services::compare_mountains(client, ids).await?;
+    for mountain in &mut result.mountains {
+        mountain.localize(language);
+    }
+    for difference in &mut result.differences {
+        difference.shared_prefectures =
+            localize_prefectures(&difference.shared_prefectures, language);
+        difference.shared_tags = localize_tags(&difference.shared_tags, language);
+    }
+    serialize_body(&result, format).map_err(serialize_failed)
+}
+
+async fn get_sub_peaks(
+    client: &Client,
+    id: &str,
+    format: &BodyFormat,
+    language: &Language,
+) -> Result<SerializedBody, ServiceError> {
+    let mut sub_peaks = services::get_sub_peaks(client, id.to_string()).await?;
+    for mountain in &mut sub_peaks {
+        mountain.localize(language);
+    }
+    let sub_peak_list = SubPeakListResponse {
+        total: sub_peaks.len(),
+        mountains: sub_peaks,
+    };
+    serialize_body(&sub_peak_list, format).map_err(serialize_failed)
+}
+
+fn get_mountain_ranges(format: &BodyFormat) -> Result<SerializedBody, ()> {
+    let ranges = MountainRangeMapper::all();
+    let range_list = MountainRangeListResponse {
+        total: ranges.len(),
+        ranges,
+    };
+    serialize_body(&range_list, format)
+}
+
+fn range_condition(id: &str) -> Option<SearchCondition> {
+    let range_key = id.parse::<u32>().ok()?;
+    let range_mapper = MountainRangeMapper::new(range_key);
+    match range_mapper.to_mountain_range() {
+        Ok(range) => Some(SearchCondition {
+            search_type: SearchType::MountainRange,
+            value: range,
+        }),
+        Err(_) => None,
+    }
+}
+
+async fn get_parks(client: &Client, format: &BodyFormat) -> Result<SerializedBody, ServiceError> {
+    let parks = services::get_park_counts(client).await?;
+    let park_list = ParkListResponse {
+        total: parks.len(),
+        parks,
+    };
+    serialize_body(&park_list, format).map_err(serialize_failed)
+}
+
+async fn get_routes(
+    client: &Client,
+    id: &str,
+    format: &BodyFormat,
+) -> Result<SerializedBody, ServiceError> {
+    let routes = services::get_routes_by_mountain_id(client, id.to_string()).await?;
+    let route_list = RouteListResponse {
+        total: routes.len(),
+        routes,
+    };
+    serialize_body(&route_list, format).map_err(serialize_failed)
+}
+
+async fn get_route(
+    client: &Client,
+    id: &str,
+    format: &BodyFormat,
+) -> Result<SerializedBody, ServiceError> {
+    let route = services::get_route_by_id(client, id.to_string()).await?;
+    serialize_body(&route, format).map_err(serialize_failed)
+}
+
+fn nearby_hut_condition(
+    query_params: &StrMap,
+) -> Result<Option<NearbyHutCondition>, Vec<ParameterError>> {
+    let latitude = query_params.get("latitude");
+    let longitude = query_params.get("longitude");
+    let radius = query_params.get("radius");
+
+    // 座標を指定しない場合は全件を返す
+    if latitude.is_none() && longitude.is_none() && radius.is_none() {
+        return Ok(None);
     }
 
-    // offset 値チェック
-    let mut offset_value = 0 as usize;
-    if let Some(offset) = query_params.get("offset") {
-        if let Ok(offset_temp) = offset.to_string().parse::<usize>() {
-            offset_value = offset_temp;
-        } else {
-            err_message_list.push("offsetは0以上の整数を指定してください。".to_string());
+    let mut err_message_list: Vec<ParameterError> = Vec::new();
+
+    let mut latitude_value = 0.0;
+    match latitude.map(|l| l.parse::<f64>()) {
+        Some(Ok(l)) if (-90.0..=90.0).contains(&l) => latitude_value = l,
+        _ => {
+            err_message_list.push(ParameterError::new("latitude", Message::InvalidLatitude));
         }
     }
 
-    // limit 値チェック
-    let mut limit_value: Option<usize> = None;
-    if let Some(limit) = query_params.get("limit") {
-        if let Ok(limit_temp) = limit.to_string().parse::<usize>() {
-            if limit_temp > 0 {
-                limit_value = Some(limit_temp);
-            } else {
-                err_message_list.push("limitは1以上の整数を指定してください。".to_string());
-            }
-        } else {
-            err_message_list.push("limitは1以上の整数を指定してください。".to_string());
+    let mut longitude_value = 0.0;
+    match longitude.map(|l| l.parse::<f64>()) {
+        Some(Ok(l)) if (-180.0..=180.0).contains(&l) => longitude_value = l,
+        _ => {
+            err_message_list.push(ParameterError::new("longitude", Message::InvalidLongitude));
         }
     }
 
-    // sort
-    let mut sort_key = "id".to_string();
-    if let Some(sort) = query_params.get("sort") {
-        let mut is_invalid_sort_value = true;
-        let chk_keys = [
-            "id.asc",
-            "id.desc",
-            "elevation.asc",
-            "elevation.desc",
-            "name.asc",
-            "name.desc",
-        ];
-        for key in chk_keys {
-            let s_key = sort.to_lowercase();
-            if s_key == key.to_string() {
-                sort_key = s_key;
-                is_invalid_sort_value = false;
+    // radius 値チェック（km）
+    let mut radius_value = 10.0;
+    if let Some(radius) = radius {
+        match radius.parse::<f64>() {
+            Ok(radius_temp) if radius_temp.is_finite() && radius_temp > 0.0 => {
+                radius_value = radius_temp;
+            }
+            _ => {
+                err_message_list.push(ParameterError::new("radius", Message::InvalidRadius));
             }
-        }
-        if is_invalid_sort_value {
-            err_message_list.push("不正なソート指定です。".to_string());
         }
     }
 
@@ -236,42 +655,105 @@ async fn search_mountains(
         return Err(err_message_list);
     }
 
-    // offset and limit
-    let range_condition = RangeCondition {
-        offset: offset_value,
-        limit: limit_value,
+    Ok(Some(NearbyHutCondition {
+        latitude: latitude_value,
+        longitude: longitude_value,
+        radius: radius_value,
+    }))
+}
+
+async fn get_huts(
+    client: &Client,
+    condition: Option<NearbyHutCondition>,
+    format: &BodyFormat,
+) -> Result<SerializedBody, ServiceError> {
+    match condition {
+        Some(nearby_condition) => {
+            let huts = services::get_nearby_huts(client, nearby_condition).await?;
+            let hut_list = NearbyHutListResponse {
+                total: huts.len(),
+                huts,
+            };
+            serialize_body(&hut_list, format).map_err(serialize_failed)
+        }
+        None => {
+            let huts = services::get_all_huts(client).await?;
+            let hut_list = HutListResponse {
+                total: huts.len(),
+                huts,
+            };
+            serialize_body(&hut_list, format).map_err(serialize_failed)
+        }
+    }
+}
+
+async fn get_hut(
+    client: &Client,
+    id: &str,
+    format: &BodyFormat,
+) -> Result<SerializedBody, ServiceError> {
+    let hut = services::get_hut_by_id(client, id.to_string()).await?;
+    serialize_body(&hut, format).map_err(serialize_failed)
+}
+
+async fn get_mountain_huts(
+    client: &Client,
+    id: &str,
+    format: &BodyFormat,
+) -> Result<SerializedBody, ServiceError> {
+    let huts = services::get_huts_by_mountain_id(client, id.to_string()).await?;
+    let hut_list = HutListResponse {
+        total: huts.len(),
+        huts,
     };
+    serialize_body(&hut_list, format).map_err(serialize_failed)
+}
 
-    // 検索条件が存在しない場合、scanを実行する
-    if search_conditions.is_empty() {
-        return match services::get_all_mountains(&client, range_condition, &sort_key).await {
-            Ok(searched_mountain_result) => {
-                match serde_json::to_string_pretty(&searched_mountain_result.mountains) {
-                    Ok(result) => Ok(SearchedResult {
-                        mountains_json: result,
-                        total: searched_mountain_result.total,
-                        offset: searched_mountain_result.offset,
-                        limit: searched_mountain_result.limit,
-                    }),
-                    Err(_) => Err(simple_err_message_list),
-                }
-            }
-            Err(_) => Err(simple_err_message_list),
-        };
+fn simplify_tolerance(query_params: &StrMap) -> Result<Option<f64>, Vec<ParameterError>> {
+    match query_params.get("simplify") {
+        Some(simplify) => match simplify.parse::<f64>() {
+            Ok(tolerance) if tolerance.is_finite() && tolerance > 0.0 => Ok(Some(tolerance)),
+            _ => Err(vec![ParameterError::new(
+                "simplify",
+                Message::InvalidSimplify,
+            )]),
+        },
+        None => Ok(None),
     }
+}
 
-    match services::search_mountains(&client, search_conditions, range_condition, &sort_key).await {
-        Ok(searched_mountain_result) => {
-            match serde_json::to_string_pretty(&searched_mountain_result.mountains) {
-                Ok(result) => Ok(SearchedResult {
-                    mountains_json: result,
-                    total: searched_mountain_result.total,
-                    offset: searched_mountain_result.offset,
-                    limit: searched_mountain_result.limit,
-                }),
-                Err(_) => Err(simple_err_message_list),
-            }
-        }
-        Err(_) => Err(simple_err_message_list),
+async fn get_route_track(
+    client: &Client,
+    id: &str,
+    simplify: Option<f64>,
+    format: &BodyFormat,
+) -> Result<Option<SerializedBody>, ServiceError> {
+    match services::get_route_track(client, id.to_string(), simplify).await? {
+        Some(feature) => serialize_body(&feature, format)
+            .map(Some)
+            .map_err(serialize_failed),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_errors_map_to_problem_status() {
+        let status = |error: ServiceError| {
+            service_problem(error, Message::MountainNotFound, &Language::Ja).status
+        };
+
+        assert_eq!(status(ServiceError::NotFound), 404);
+        assert_eq!(status(ServiceError::OffsetOutOfRange), 400);
+        // 標高順位の算出など、山岳の取得後の失敗は 404 にしない
+        assert_eq!(
+            status(ServiceError::Internal(
+                "標高インデックスを構築できません。".to_string()
+            )),
+            500
+        );
     }
 }
@@ -0,0 +1,109 @@
+use crate::models::{get_value, ValueType};
+use aws_sdk_dynamodb::model::AttributeValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 山岳の別名（例: 御嶽山 に対する 木曽御嶽山）
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Alias {
+    pub(crate) name: String,
+    pub(crate) name_kana: String,
+    pub(crate) kind: String,
+}
+
+/// "Alias" 項目の AliasValues（Name, NameKana, Kind のリスト）から別名を取得する
+///
+/// 別名は山岳毎に DataType = "Alias" の項目1件の AliasValues にのみ格納する。
+/// 名称検索では DataType_Id_Index で "Alias" 項目を取得し、alias_matches で絞り込む。
+pub(crate) fn get_aliases(alias_value: &HashMap<String, AttributeValue>) -> Vec<Alias> {
+    let name_key = String::from("Name");
+    let kana_key = String::from("NameKana");
+    let kind_key = String::from("Kind");
+
+    let mut aliases: Vec<Alias> = Vec::new();
+    if let Some(value_attr) = alias_value.get("AliasValues") {
+        if let Ok(alias_values) = value_attr.as_l() {
+            for alias_attr in alias_values {
+                if let Ok(data_value) = alias_attr.as_m() {
+                    aliases.push(Alias {
+                        name: get_value(data_value, &name_key, ValueType::String),
+                        name_kana: get_value(data_value, &kana_key, ValueType::String),
+                        kind: get_value(data_value, &kind_key, ValueType::String),
+                    });
+                }
+            }
+        }
+    }
+
+    aliases
+}
+
+/// いずれかの別名、またはそのよみが value を含むか（別名をまたいで一致させない）
+pub(crate) fn alias_matches(aliases: &[Alias], value: &str) -> bool {
+    aliases
+        .iter()
+        .any(|alias| alias.name.contains(value) || alias.name_kana.contains(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias_attr(name: &str, name_kana: &str, kind: &str) -> AttributeValue {
+        let mut data_value: HashMap<String, AttributeValue> = HashMap::new();
+        data_value.insert("Name".to_string(), AttributeValue::S(name.to_string()));
+        data_value.insert(
+            "NameKana".to_string(),
+            AttributeValue::S(name_kana.to_string()),
+        );
+        data_value.insert("Kind".to_string(), AttributeValue::S(kind.to_string()));
+        AttributeValue::M(data_value)
+    }
+
+    fn aliases() -> Vec<Alias> {
+        let mut item: HashMap<String, AttributeValue> = HashMap::new();
+        item.insert(
+            "AliasValues".to_string(),
+            AttributeValue::L(vec![
+                alias_attr("木曽御嶽山", "きそおんたけさん", "別名"),
+                alias_attr("王滝頂上", "おうたきちょうじょう", "峰名"),
+            ]),
+        );
+        get_aliases(&item)
+    }
+
+    #[test]
+    fn aliases_are_read_from_alias_values() {
+        assert_eq!(
+            aliases(),
+            vec![
+                Alias {
+                    name: "木曽御嶽山".to_string(),
+                    name_kana: "きそおんたけさん".to_string(),
+                    kind: "別名".to_string(),
+                },
+                Alias {
+                    name: "王滝頂上".to_string(),
+                    name_kana: "おうたきちょうじょう".to_string(),
+                    kind: "峰名".to_string(),
+                },
+            ]
+        );
+        assert!(get_aliases(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn alias_matches_within_one_alias() {
+        let aliases = aliases();
+        assert!(alias_matches(&aliases, "御嶽"));
+        assert!(alias_matches(&aliases, "王滝頂上"));
+        assert!(alias_matches(&aliases, "おんたけ"));
+        assert!(!alias_matches(&aliases, "乗鞍"));
+        // 別名の区切りをまたぐ文字列には一致しない
+        assert!(!alias_matches(&aliases, "山/王滝"));
+        assert!(!alias_matches(&aliases, "山王滝"));
+        assert!(!alias_matches(&[], "御嶽"));
+    }
+}
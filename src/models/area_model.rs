@@ -0,0 +1,72 @@
+pub struct AreaMapper;
+
+struct Area {
+    name: &'static str,
+    name_en: &'static str,
+}
+
+impl AreaMapper {
+    /// 地域名から英語の地域名を取得する（「地方」の有無は問わない）
+    pub fn find_english_name(name: &str) -> Option<&'static str> {
+        let name = name.trim_end_matches("地方");
+        AreaMapper::AREAS
+            .iter()
+            .find(|area| area.name == name)
+            .map(|area| area.name_en)
+    }
+
+    const AREAS: [Area; 13] = [
+        Area {
+            name: "北海道",
+            name_en: "Hokkaido",
+        },
+        Area {
+            name: "東北",
+            name_en: "Tohoku",
+        },
+        Area {
+            name: "関東",
+            name_en: "Kanto",
+        },
+        Area {
+            name: "中部",
+            name_en: "Chubu",
+        },
+        Area {
+            name: "甲信越",
+            name_en: "Koshinetsu",
+        },
+        Area {
+            name: "北陸",
+            name_en: "Hokuriku",
+        },
+        Area {
+            name: "東海",
+            name_en: "Tokai",
+        },
+        Area {
+            name: "近畿",
+            name_en: "Kinki",
+        },
+        Area {
+            name: "関西",
+            name_en: "Kansai",
+        },
+        Area {
+            name: "中国",
+            name_en: "Chugoku",
+        },
+        Area {
+            name: "四国",
+            name_en: "Shikoku",
+        },
+        Area {
+            name: "九州",
+            name_en: "Kyushu",
+        },
+        Area {
+            name: "沖縄",
+            name_en: "Okinawa",
+        },
+    ];
+}
@@ -0,0 +1,143 @@
+use crate::models::Mountain;
+
+/// 複数の値を持つ項目（都道府県、タグなど）の区切り文字
+pub const CSV_VALUE_DELIMITER: &str = "|";
+
+const CSV_HEADER: [&str; 14] = [
+    "id",
+    "name",
+    "nameKana",
+    "aliases",
+    "area",
+    "range",
+    "prefectures",
+    "primaryPrefecture",
+    "parks",
+    "elevation",
+    "latitude",
+    "longitude",
+    "gsiUrl",
+    "tags",
+];
+
+/// 山岳一覧を RFC 4180 形式の CSV に変換する（改行は CRLF）
+pub fn to_mountains_csv(mountains: &[Mountain], with_bom: bool) -> String {
+    let mut csv = String::new();
+    if with_bom {
+        csv.push('\u{feff}');
+    }
+
+    push_record(&mut csv, CSV_HEADER.iter().map(|h| h.to_string()).collect());
+
+    for mountain in mountains {
+        let aliases: Vec<String> = mountain
+            .aliases
+            .iter()
+            .map(|alias| alias.name.to_string())
+            .collect();
+        let range = match &mountain.range {
+            Some(range) => range.name.to_string(),
+            None => "".to_string(),
+        };
+
+        push_record(
+            &mut csv,
+            vec![
+                mountain.id.to_string(),
+                mountain.name.to_string(),
+                mountain.name_kana.to_string(),
+                aliases.join(CSV_VALUE_DELIMITER),
+                mountain.area.to_string(),
+                range,
+                mountain.prefectures.join(CSV_VALUE_DELIMITER),
+                mountain.primary_prefecture.clone().unwrap_or_default(),
+                mountain.parks.join(CSV_VALUE_DELIMITER),
+                mountain.elevation.to_string(),
+                mountain.location.latitude.to_string(),
+                mountain.location.longitude.to_string(),
+                mountain.location.gsi_url.to_string(),
+                mountain.tags.join(CSV_VALUE_DELIMITER),
+            ],
+        );
+    }
+
+    csv
+}
+
+fn push_record(csv: &mut String, fields: Vec<String>) {
+    let escaped: Vec<String> = fields.iter().map(|field| escape_field(field)).collect();
+    csv.push_str(&escaped.join(","));
+    csv.push_str("\r\n");
+}
+
+/// カンマ、ダブルクォート、改行を含む項目はダブルクォートで囲む
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Location;
+
+    #[test]
+    fn escape_fields_per_rfc4180() {
+        assert_eq!(escape_field("富士山"), "富士山");
+        assert_eq!(escape_field(""), "");
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("line1\r\nline2"), "\"line1\r\nline2\"");
+        assert_eq!(escape_field("line1\nline2"), "\"line1\nline2\"");
+        assert_eq!(escape_field("cr\r"), "\"cr\r\"");
+    }
+
+    fn mountain() -> Mountain {
+        Mountain {
+            id: 1,
+            name: "富士山".to_string(),
+            name_kana: "ふじさん".to_string(),
+            area: "中部".to_string(),
+            prefectures: vec!["山梨県".to_string(), "静岡県".to_string()],
+            primary_prefecture: Some("山梨県".to_string()),
+            elevation: 3776.0,
+            location: Location {
+                latitude: 35.360556,
+                longitude: 138.727778,
+                gsi_url: "https://maps.gsi.go.jp/#15/35.360556/138.727778".to_string(),
+            },
+            tags: vec!["百名山".to_string(), "日本の\"象徴\", 霊峰".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn mountains_csv_has_header_and_crlf_records() {
+        let csv = to_mountains_csv(&[mountain()], false);
+        let records: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], CSV_HEADER.join(","));
+        assert_eq!(
+            records[1],
+            "1,富士山,ふじさん,,中部,,山梨県|静岡県,山梨県,,3776,35.360556,138.727778,\
+             https://maps.gsi.go.jp/#15/35.360556/138.727778,\"百名山|日本の\"\"象徴\"\", 霊峰\""
+        );
+        // 最終行も CRLF で終わる
+        assert_eq!(records[2], "");
+        assert!(!csv.contains('\u{feff}'));
+    }
+
+    #[test]
+    fn bom_is_prepended_only_once() {
+        let csv = to_mountains_csv(&[mountain(), mountain()], true);
+        assert!(csv.starts_with("\u{feff}id,name,"));
+        assert_eq!(csv.matches('\u{feff}').count(), 1);
+
+        let empty = to_mountains_csv(&[], true);
+        assert_eq!(empty, format!("\u{feff}{}\r\n", CSV_HEADER.join(",")));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::models::{get_value, ValueType};
+use aws_sdk_dynamodb::model::AttributeValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 技術的難易度（A が易しく E が難しい）
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema, Clone, Copy,
+)]
+pub enum TechnicalGrade {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+/// 体力度（1〜10）
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema, Clone, Copy,
+)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct PhysicalGrade(#[schemars(range(min = 1, max = 10))] u8);
+
+/// 難易度（技術的難易度、体力度の順に比較する）
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema, Clone, Copy,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Difficulty {
+    pub technical: TechnicalGrade,
+    pub physical: PhysicalGrade,
+}
+
+/// 山岳のルート全体の難易度
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DifficultySummary {
+    /// 最も易しいルートの難易度
+    pub min: Difficulty,
+    /// 最も難しいルートの難易度
+    pub max: Difficulty,
+}
+
+/// difficulty_max で指定する難易度の上限（C または C5 の形式。体力度は省略できる）
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DifficultyLimit {
+    pub technical: TechnicalGrade,
+    pub physical: Option<PhysicalGrade>,
+}
+
+impl PhysicalGrade {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 10;
+}
+
+impl TryFrom<u8> for PhysicalGrade {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if (PhysicalGrade::MIN..=PhysicalGrade::MAX).contains(&value) {
+            Ok(PhysicalGrade(value))
+        } else {
+            Err(format!(
+                "体力度は{}から{}の整数です。",
+                PhysicalGrade::MIN,
+                PhysicalGrade::MAX
+            ))
+        }
+    }
+}
+
+impl From<PhysicalGrade> for u8 {
+    fn from(grade: PhysicalGrade) -> Self {
+        grade.0
+    }
+}
+
+impl TryFrom<&str> for TechnicalGrade {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_uppercase().as_str() {
+            "A" => Ok(TechnicalGrade::A),
+            "B" => Ok(TechnicalGrade::B),
+            "C" => Ok(TechnicalGrade::C),
+            "D" => Ok(TechnicalGrade::D),
+            "E" => Ok(TechnicalGrade::E),
+            _ => Err("技術的難易度はAからEです。".to_string()),
+        }
+    }
+}
+
+impl TryFrom<&str> for DifficultyLimit {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let error = "難易度の上限は技術的難易度、または技術的難易度と体力度です。".to_string();
+        let mut chars = value.chars();
+        let technical = match chars.next() {
+            Some(grade) => TechnicalGrade::try_from(grade.to_string().as_str())?,
+            None => return Err(error),
+        };
+
+        let physical = chars.as_str();
+        if physical.is_empty() {
+            return Ok(DifficultyLimit {
+                technical,
+                physical: None,
+            });
+        }
+        // "05" や "+5" は受け付けない
+        if physical.starts_with('0') || !physical.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error);
+        }
+        match physical.parse::<u8>() {
+            Ok(grade) => Ok(DifficultyLimit {
+                technical,
+                physical: Some(PhysicalGrade::try_from(grade)?),
+            }),
+            Err(_) => Err(error),
+        }
+    }
+}
+
+impl DifficultyLimit {
+    /// 技術的難易度と（指定されていれば）体力度の両方が上限以下かどうか
+    pub fn allows(&self, difficulty: &Difficulty) -> bool {
+        difficulty.technical <= self.technical
+            && self
+                .physical
+                .is_none_or(|physical| difficulty.physical <= physical)
+    }
+}
+
+impl DifficultySummary {
+    pub fn from_difficulties(difficulties: &[Difficulty]) -> Option<Self> {
+        let min = difficulties.iter().min()?;
+        let max = difficulties.iter().max()?;
+        Some(DifficultySummary {
+            min: *min,
+            max: *max,
+        })
+    }
+}
+
+/// ルートの項目から難易度を取得する（どちらかが不正な場合は None）
+pub(crate) fn get_difficulty(item: &HashMap<String, AttributeValue>) -> Option<Difficulty> {
+    let technical_key = String::from("TechnicalGrade");
+    let physical_key = String::from("PhysicalGrade");
+
+    let technical =
+        TechnicalGrade::try_from(get_value(item, &technical_key, ValueType::String).as_str())
+            .ok()?;
+    let physical = get_value(item, &physical_key, ValueType::Number)
+        .parse::<u8>()
+        .ok()
+        .and_then(|grade| PhysicalGrade::try_from(grade).ok())?;
+
+    Some(Difficulty {
+        technical,
+        physical,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn difficulty(technical: TechnicalGrade, physical: u8) -> Difficulty {
+        Difficulty {
+            technical,
+            physical: PhysicalGrade::try_from(physical).unwrap(),
+        }
+    }
+
+    #[test]
+    fn parse_grades() {
+        assert_eq!(TechnicalGrade::try_from("a"), Ok(TechnicalGrade::A));
+        assert_eq!(TechnicalGrade::try_from("E"), Ok(TechnicalGrade::E));
+        assert!(TechnicalGrade::try_from("F").is_err());
+        assert!(TechnicalGrade::try_from("").is_err());
+
+        assert!(PhysicalGrade::try_from(0).is_err());
+        assert_eq!(u8::from(PhysicalGrade::try_from(1).unwrap()), 1);
+        assert_eq!(u8::from(PhysicalGrade::try_from(10).unwrap()), 10);
+        assert!(PhysicalGrade::try_from(11).is_err());
+    }
+
+    #[test]
+    fn parse_difficulty_limit() {
+        assert_eq!(
+            DifficultyLimit::try_from("c"),
+            Ok(DifficultyLimit {
+                technical: TechnicalGrade::C,
+                physical: None,
+            })
+        );
+        assert_eq!(
+            DifficultyLimit::try_from("B10"),
+            Ok(DifficultyLimit {
+                technical: TechnicalGrade::B,
+                physical: Some(PhysicalGrade::try_from(10).unwrap()),
+            })
+        );
+        for invalid in ["", "F", "5", "C0", "C05", "C11", "C+5", "CC", "C 5"] {
+            assert!(DifficultyLimit::try_from(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn difficulties_are_ordered_by_technical_then_physical() {
+        assert!(difficulty(TechnicalGrade::A, 9) < difficulty(TechnicalGrade::B, 1));
+        assert!(difficulty(TechnicalGrade::B, 2) < difficulty(TechnicalGrade::B, 3));
+
+        let summary = DifficultySummary::from_difficulties(&[
+            difficulty(TechnicalGrade::B, 3),
+            difficulty(TechnicalGrade::A, 9),
+            difficulty(TechnicalGrade::D, 2),
+        ])
+        .unwrap();
+        assert_eq!(summary.min, difficulty(TechnicalGrade::A, 9));
+        assert_eq!(summary.max, difficulty(TechnicalGrade::D, 2));
+        assert_eq!(DifficultySummary::from_difficulties(&[]), None);
+    }
+
+    #[test]
+    fn limit_checks_physical_grade_when_given() {
+        let technical_only = DifficultyLimit::try_from("B").unwrap();
+        assert!(technical_only.allows(&difficulty(TechnicalGrade::B, 10)));
+        assert!(!technical_only.allows(&difficulty(TechnicalGrade::C, 1)));
+
+        let both = DifficultyLimit::try_from("B4").unwrap();
+        assert!(both.allows(&difficulty(TechnicalGrade::A, 4)));
+        assert!(!both.allows(&difficulty(TechnicalGrade::A, 5)));
+        assert!(!both.allows(&difficulty(TechnicalGrade::C, 1)));
+    }
+
+    #[test]
+    fn serialize_difficulty() {
+        let value = serde_json::to_value(difficulty(TechnicalGrade::C, 5)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "technical": "C", "physical": 5 })
+        );
+        assert!(serde_json::from_value::<Difficulty>(
+            serde_json::json!({ "technical": "C", "physical": 11 })
+        )
+        .is_err());
+    }
+}
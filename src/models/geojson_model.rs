@@ -0,0 +1,181 @@
+use crate::models::Mountain;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// GeoJSON (RFC 7946) の Feature
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct Feature<P: Serialize> {
+    #[serde(rename = "type")]
+    feature_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub geometry: Geometry,
+    pub properties: P,
+}
+
+/// GeoJSON の FeatureCollection（ページング情報などは foreign members として出力する）
+#[derive(Debug, Serialize, Clone)]
+pub struct FeatureCollection<P: Serialize, M: Serialize> {
+    #[serde(rename = "type")]
+    collection_type: &'static str,
+    pub features: Vec<Feature<P>>,
+    #[serde(flatten)]
+    pub foreign_members: M,
+}
+
+/// GeoJSON のジオメトリ（座標は [経度, 緯度, 標高] の順）
+#[derive(Debug, PartialEq, Serialize, JsonSchema, Clone)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: Vec<f64> },
+    LineString { coordinates: Vec<Vec<f64>> },
+}
+
+impl<P: Serialize> Feature<P> {
+    pub fn new(geometry: Geometry, properties: P) -> Self {
+        Self {
+            feature_type: "Feature",
+            id: None,
+            geometry,
+            properties,
+        }
+    }
+
+    pub fn with_id(mut self, id: u32) -> Self {
+        self.id = Some(id);
+        self
+    }
+}
+
+impl<P: Serialize, M: Serialize> FeatureCollection<P, M> {
+    pub fn new(features: Vec<Feature<P>>, foreign_members: M) -> Self {
+        Self {
+            collection_type: "FeatureCollection",
+            features,
+            foreign_members,
+        }
+    }
+}
+
+/// 山岳を Point の Feature に変換する（location 以外の項目は properties に格納する）
+#[allow(clippy::result_unit_err)]
+pub fn to_mountain_feature(mountain: &Mountain) -> Result<Feature<Map<String, Value>>, ()> {
+    let mut properties = match serde_json::to_value(mountain) {
+        Ok(Value::Object(properties)) => properties,
+        _ => return Err(()),
+    };
+    properties.remove("location");
+    properties.insert(
+        "gsiUrl".to_string(),
+        Value::String(mountain.location.gsi_url.to_string()),
+    );
+
+    let geometry = Geometry::Point {
+        coordinates: vec![
+            mountain.location.longitude,
+            mountain.location.latitude,
+            mountain.elevation,
+        ],
+    };
+
+    Ok(Feature::new(geometry, properties).with_id(mountain.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Location;
+    use serde_json::json;
+
+    fn mountain() -> Mountain {
+        Mountain {
+            id: 1,
+            name: "富士山".to_string(),
+            elevation: 3776.0,
+            location: Location {
+                latitude: 35.3606,
+                longitude: 138.7274,
+                gsi_url: "https://maps.gsi.go.jp/#15/35.3606/138.7274".to_string(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn point_coordinates_are_longitude_latitude_elevation() {
+        let feature = to_mountain_feature(&mountain()).unwrap();
+        assert_eq!(
+            feature.geometry,
+            Geometry::Point {
+                coordinates: vec![138.7274, 35.3606, 3776.0],
+            }
+        );
+    }
+
+    #[test]
+    fn location_is_moved_out_of_properties() {
+        let feature = serde_json::to_value(to_mountain_feature(&mountain()).unwrap()).unwrap();
+
+        assert_eq!(feature["type"], "Feature");
+        assert_eq!(feature["id"], 1);
+        assert_eq!(feature["geometry"]["type"], "Point");
+
+        let properties = &feature["properties"];
+        assert_eq!(properties["id"], 1);
+        assert_eq!(properties["name"], "富士山");
+        assert_eq!(properties["elevation"], 3776);
+        assert_eq!(
+            properties["gsiUrl"],
+            "https://maps.gsi.go.jp/#15/35.3606/138.7274"
+        );
+        assert!(properties.get("location").is_none());
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Paging {
+        total: usize,
+        offset: usize,
+        limit: Option<usize>,
+    }
+
+    #[test]
+    fn paging_is_output_as_foreign_members() {
+        let features = vec![to_mountain_feature(&mountain()).unwrap()];
+        let paging = Paging {
+            total: 10,
+            offset: 5,
+            limit: Some(1),
+        };
+        let collection = serde_json::to_value(FeatureCollection::new(features, paging)).unwrap();
+
+        assert_eq!(collection["type"], "FeatureCollection");
+        assert_eq!(collection["features"].as_array().unwrap().len(), 1);
+        assert_eq!(collection["total"], 10);
+        assert_eq!(collection["offset"], 5);
+        assert_eq!(collection["limit"], 1);
+
+        // limit 未指定の場合は null とする
+        let paging = Paging {
+            total: 0,
+            offset: 0,
+            limit: None,
+        };
+        let collection = serde_json::to_value(FeatureCollection::<Map<String, Value>, _>::new(
+            vec![],
+            paging,
+        ))
+        .unwrap();
+        assert_eq!(
+            collection,
+            json!({
+                "type": "FeatureCollection",
+                "features": [],
+                "total": 0,
+                "offset": 0,
+                "limit": null,
+            })
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use crate::models::{get_location, get_value, Location, ValueType};
+use aws_sdk_dynamodb::model::AttributeValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub trait HutBaseMapper {
+    fn new(data: HashMap<String, AttributeValue>) -> Self;
+    fn to_hut(&self) -> Hut;
+}
+
+/// 山小屋を格納するテーブル
+///
+/// Mountains テーブルの Id（数値）は山岳IDのため、山小屋は別テーブルに山小屋IDを Id とする
+/// 1件の項目として格納し、紐づく山岳IDは MountainIds（数値セット）に格納する。
+pub const HUT_TABLE: &str = "Huts";
+
+pub struct HutMapper {
+    data: HashMap<String, AttributeValue>,
+}
+
+/// 山小屋
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Hut {
+    pub(crate) id: u32,
+    name: String,
+    name_kana: String,
+    elevation: f64,
+    pub(crate) location: Location,
+    /// 収容人数
+    capacity: Option<u32>,
+    /// 営業期間
+    season: String,
+    /// 紐づく山岳ID（ID順）
+    pub(crate) mountains: Vec<u32>,
+}
+
+impl HutBaseMapper for HutMapper {
+    fn new(data: HashMap<String, AttributeValue>) -> Self {
+        Self { data }
+    }
+
+    fn to_hut(&self) -> Hut {
+        let item = &self.data;
+
+        let mut hut = Hut {
+            id: 0,
+            name: get_value(item, &String::from("HutName"), ValueType::String),
+            name_kana: get_value(item, &String::from("HutNameKana"), ValueType::String),
+            elevation: 0.0,
+            location: get_location(item, "LocationValue"),
+            capacity: None,
+            season: get_value(item, &String::from("Season"), ValueType::String),
+            mountains: vec![],
+        };
+
+        let key = String::from("Id");
+        if let Ok(id) = get_value(item, &key, ValueType::Number).parse::<u32>() {
+            hut.id = id;
+        }
+
+        let key = String::from("ElevationValue");
+        if let Ok(elevation) = get_value(item, &key, ValueType::Number).parse::<f64>() {
+            hut.elevation = elevation;
+        }
+
+        let key = String::from("CapacityValue");
+        if let Ok(capacity) = get_value(item, &key, ValueType::Number).parse::<u32>() {
+            hut.capacity = Some(capacity);
+        }
+
+        if let Some(value_attr) = item.get("MountainIds") {
+            if let Ok(mountain_ids) = value_attr.as_ns() {
+                for mountain_id in mountain_ids {
+                    if let Ok(mountain_id) = mountain_id.parse::<u32>() {
+                        hut.mountains.push(mountain_id);
+                    }
+                }
+            }
+        }
+        hut.mountains.sort_unstable();
+        hut.mountains.dedup();
+
+        hut
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(value: &str) -> AttributeValue {
+        AttributeValue::N(value.to_string())
+    }
+
+    fn string(value: &str) -> AttributeValue {
+        AttributeValue::S(value.to_string())
+    }
+
+    fn hut_item(attributes: &[(&str, AttributeValue)]) -> HashMap<String, AttributeValue> {
+        attributes
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn hut_is_mapped_from_item() {
+        let mut location: HashMap<String, AttributeValue> = HashMap::new();
+        location.insert("Latitude".to_string(), number("35.3606"));
+        location.insert("Longitude".to_string(), number("138.7274"));
+        location.insert("GsiUrl".to_string(), string("https://maps.gsi.go.jp/"));
+
+        let hut = HutMapper::new(hut_item(&[
+            ("Id", number("12")),
+            ("HutName", string("頂上富士館")),
+            ("HutNameKana", string("ちょうじょうふじかん")),
+            ("ElevationValue", number("3710")),
+            ("LocationValue", AttributeValue::M(location)),
+            ("CapacityValue", number("250")),
+            ("Season", string("7月上旬〜9月上旬")),
+            (
+                "MountainIds",
+                AttributeValue::Ns(vec!["3".to_string(), "1".to_string(), "3".to_string()]),
+            ),
+        ]))
+        .to_hut();
+
+        assert_eq!(hut.id, 12);
+        assert_eq!(hut.name, "頂上富士館");
+        assert_eq!(hut.name_kana, "ちょうじょうふじかん");
+        assert_eq!(hut.elevation, 3710.0);
+        assert_eq!(hut.location.latitude, 35.3606);
+        assert_eq!(hut.location.longitude, 138.7274);
+        assert_eq!(hut.capacity, Some(250));
+        assert_eq!(hut.season, "7月上旬〜9月上旬");
+        // 紐づく山岳IDはID順に重複を除く
+        assert_eq!(hut.mountains, vec![1, 3]);
+    }
+
+    #[test]
+    fn missing_optional_values_are_defaulted() {
+        let hut = HutMapper::new(hut_item(&[
+            ("Id", number("7")),
+            ("HutName", string("無人小屋")),
+            ("CapacityValue", string("不明")),
+        ]))
+        .to_hut();
+
+        assert_eq!(hut.id, 7);
+        assert_eq!(hut.elevation, 0.0);
+        assert_eq!(hut.capacity, None);
+        assert!(hut.mountains.is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 地球の平均半径（km）
+pub(crate) const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// 16方位
+const DIRECTIONS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub(crate) latitude: f64,
+    pub(crate) longitude: f64,
+    pub(crate) gsi_url: String,
+}
+
+impl Location {
+    /// 大円距離（km）をハーバーサイン公式で求める
+    pub fn distance_to(&self, other: &Location) -> f64 {
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let d_lat = (other.latitude - self.latitude).to_radians();
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+
+        EARTH_RADIUS_KM * c
+    }
+
+    /// 真北を0度とした時計回りの初期方位角（0以上360未満）を求める
+    pub fn bearing_to(&self, other: &Location) -> f64 {
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let y = d_lon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+
+        (y.atan2(x).to_degrees() + 360.0) % 360.0
+    }
+}
+
+/// 方位角を16方位の文字列に変換する
+pub fn to_direction(bearing: f64) -> String {
+    let index = ((bearing.rem_euclid(360.0) / 22.5).round() as usize) % DIRECTIONS.len();
+    DIRECTIONS[index].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(latitude: f64, longitude: f64) -> Location {
+        Location {
+            latitude,
+            longitude,
+            gsi_url: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn distance_between_known_points() {
+        // 東京駅 - 大阪駅 は約403km
+        let tokyo = location(35.681236, 139.767125);
+        let osaka = location(34.702485, 135.495951);
+        assert!((tokyo.distance_to(&osaka) - 403.06).abs() < 0.1);
+        assert!((osaka.distance_to(&tokyo) - 403.06).abs() < 0.1);
+
+        // 富士山 - 東京駅 は約100km
+        let fuji = location(35.360556, 138.727778);
+        assert!((fuji.distance_to(&tokyo) - 100.59).abs() < 0.1);
+
+        // 赤道上の経度1度は約111.19km
+        assert!((location(0.0, 0.0).distance_to(&location(0.0, 1.0)) - 111.19).abs() < 0.01);
+        assert_eq!(tokyo.distance_to(&tokyo), 0.0);
+    }
+
+    #[test]
+    fn bearing_between_known_points() {
+        let origin = location(0.0, 0.0);
+        assert!((origin.bearing_to(&location(1.0, 0.0)) - 0.0).abs() < 1e-9);
+        assert!((origin.bearing_to(&location(0.0, 1.0)) - 90.0).abs() < 1e-9);
+        assert!((origin.bearing_to(&location(-1.0, 0.0)) - 180.0).abs() < 1e-9);
+        assert!((origin.bearing_to(&location(0.0, -1.0)) - 270.0).abs() < 1e-9);
+
+        // 富士山から見た東京駅は東北東
+        let fuji = location(35.360556, 138.727778);
+        let tokyo = location(35.681236, 139.767125);
+        let bearing = fuji.bearing_to(&tokyo);
+        assert!((bearing - 68.94).abs() < 0.01);
+        assert_eq!(to_direction(bearing), "ENE");
+    }
+
+    #[test]
+    fn direction_boundaries() {
+        assert_eq!(to_direction(0.0), "N");
+        assert_eq!(to_direction(11.24), "N");
+        assert_eq!(to_direction(11.25), "NNE");
+        assert_eq!(to_direction(90.0), "E");
+        assert_eq!(to_direction(191.25), "SSW");
+        assert_eq!(to_direction(348.74), "NNW");
+        assert_eq!(to_direction(348.75), "N");
+        assert_eq!(to_direction(359.9), "N");
+        assert_eq!(to_direction(-90.0), "W");
+        assert_eq!(to_direction(450.0), "E");
+    }
+}
@@ -1,7 +1,43 @@
+mod alias_model;
+mod area_model;
+mod csv_model;
+mod difficulty_model;
+mod geojson_model;
+mod hut_model;
+mod location_model;
+mod mountain_list_model;
 mod mountain_model;
+mod mountain_range_model;
+mod park_model;
 mod prefecture_model;
+mod problem_model;
+mod rank_model;
+mod route_model;
+mod season_model;
+mod survey_point_model;
 mod tag_model;
+mod track_model;
+mod volcano_model;
+mod waypoint_model;
 
+pub use alias_model::*;
+pub use area_model::*;
+pub use csv_model::*;
+pub use difficulty_model::*;
+pub use geojson_model::*;
+pub use hut_model::*;
+pub use location_model::*;
+pub use mountain_list_model::*;
 pub use mountain_model::*;
+pub use mountain_range_model::*;
+pub use park_model::*;
 pub use prefecture_model::*;
+pub use problem_model::*;
+pub use rank_model::*;
+pub use route_model::*;
+pub use season_model::*;
+pub use survey_point_model::*;
 pub use tag_model::*;
+pub use track_model::*;
+pub use volcano_model::*;
+pub use waypoint_model::*;
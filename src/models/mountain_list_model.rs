@@ -0,0 +1,14 @@
+use crate::models::Mountain;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// 山岳一覧のレスポンス
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MountainListResponse<'a> {
+    pub mountains: &'a [Mountain],
+    /// 絞り込み後の件数（offset、limit 適用前）
+    pub total: usize,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
@@ -1,10 +1,19 @@
+use crate::i18n::Language;
+use crate::models::{
+    get_access, get_aliases, get_difficulty, get_road_closure, get_season, get_survey_point,
+    Access, Alias, AreaMapper, Difficulty, DifficultySummary, Location, MountainRange,
+    MountainRangeMapper, PrefectureMapper, Rank, RoadClosure, Season, SurveyPoint, TagMapper,
+    Volcano, MAX_ALERT_LEVEL,
+};
 use aws_sdk_dynamodb::model::AttributeValue;
-use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 
 pub trait MountainBaseMapper {
     fn new(data: Vec<HashMap<String, AttributeValue>>) -> Self;
-    fn to_mountain(&self) -> Mountain;
+    /// 標高を取得できない場合は原因を含むエラーとする
+    fn to_mountain(&self) -> Result<Mountain, String>;
 }
 
 pub struct MountainMapper {
@@ -14,27 +23,45 @@ pub struct MountainMapper {
 pub enum ValueType {
     String,
     Number,
+    Bool,
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Mountain {
     pub(crate) id: u32,
-    name: String,
+    pub(crate) name: String,
     pub name_kana: String,
-    area: String,
-    prefectures: Vec<String>,
-    pub elevation: u32,
-    location: Location,
-    tags: Vec<String>,
-}
-
-#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct Location {
-    latitude: f64,
-    longitude: f64,
-    gsi_url: String,
+    pub(crate) area: String,
+    pub(crate) range: Option<MountainRange>,
+    /// 主たる都道府県を先頭に、以降は都道府県ID順
+    pub(crate) prefectures: Vec<String>,
+    pub(crate) primary_prefecture: Option<String>,
+    pub(crate) parks: Vec<String>,
+    /// 主たる標高（ソート、絞り込み、順位に用いる）
+    ///
+    /// 小数の標高に対応するため f64 とし、整数の場合は従来どおり整数として出力する。
+    #[serde(serialize_with = "serialize_elevation")]
+    pub elevation: f64,
+    pub(crate) survey_points: Vec<SurveyPoint>,
+    pub location: Location,
+    pub(crate) tags: Vec<String>,
+    pub(crate) aliases: Vec<Alias>,
+    pub(crate) parent: Option<u32>,
+    // 親子関係は子の Parent 項目にのみ格納し、sub_peaks はそこから導出する
+    /// 子の山岳ID（ID順）
+    pub(crate) sub_peaks: Vec<u32>,
+    pub(crate) volcano: Option<Volcano>,
+    pub(crate) season: Option<Season>,
+    pub(crate) road_closures: Vec<RoadClosure>,
+    pub(crate) access: Vec<Access>,
+    pub(crate) difficulty: Option<DifficultySummary>,
+    /// 各ルートの難易度（difficulty_max の絞り込みに用いる）
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub(crate) route_difficulties: Vec<Difficulty>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub rank: Option<Rank>,
 }
 
 impl MountainBaseMapper for MountainMapper {
@@ -42,37 +69,52 @@ impl MountainBaseMapper for MountainMapper {
         Self { data }
     }
 
-    fn to_mountain(&self) -> Mountain {
+    fn to_mountain(&self) -> Result<Mountain, String> {
         let mut mountain = Mountain {
             id: 0,
             name: "".to_string(),
             name_kana: "".to_string(),
             area: "".to_string(),
+            range: None,
             prefectures: vec![],
-            elevation: 0,
+            primary_prefecture: None,
+            parks: vec![],
+            elevation: 0.0,
+            survey_points: vec![],
             location: Location {
                 latitude: 0.0,
                 longitude: 0.0,
                 gsi_url: "".to_string(),
             },
             tags: vec![],
+            aliases: vec![],
+            parent: None,
+            sub_peaks: vec![],
+            volcano: None,
+            season: None,
+            road_closures: vec![],
+            access: vec![],
+            difficulty: None,
+            route_difficulties: vec![],
+            rank: None,
         };
 
-        match self.data.get(0) {
-            Some(top_data) => {
-                let key = String::from("Id");
-                match get_value(top_data, &key, ValueType::Number).parse() {
-                    Ok(id) => mountain.id = id,
-                    Err(_) => {}
-                };
-            }
-            _ => {}
+        if let Some(top_data) = self.data.first() {
+            let key = String::from("Id");
+            if let Ok(id) = get_value(top_data, &key, ValueType::Number).parse() {
+                mountain.id = id
+            };
         }
 
+        // ルートの難易度から山岳の難易度を集計する
+        let mut route_difficulties: Vec<Difficulty> = Vec::new();
+        let mut elevation: Option<f64> = None;
+        let mut invalid_elevations: Vec<String> = Vec::new();
+
         for item in &self.data {
-            match item.get(&*"DataType") {
-                Some(type_attr) => match type_attr.as_s() {
-                    Ok(data_type) => match data_type.as_str() {
+            if let Some(type_attr) = item.get("DataType") {
+                if let Ok(data_type) = type_attr.as_s() {
+                    match data_type.as_str() {
                         "Name" => {
                             let key = String::from("DataValue");
                             mountain.name = get_value(item, &key, ValueType::String);
@@ -83,39 +125,185 @@ impl MountainBaseMapper for MountainMapper {
                         }
                         "Elevation" => {
                             let key = String::from("ElevationValue");
-                            if let Ok(elevation_value) =
-                                get_value(item, &key, ValueType::Number).parse::<u32>()
-                            {
-                                mountain.elevation = elevation_value;
+                            let elevation_value = get_value(item, &key, ValueType::Number);
+                            match elevation_value.parse::<f64>() {
+                                Ok(value) => elevation = Some(value),
+                                Err(_) => invalid_elevations.push(elevation_value),
                             }
                         }
                         "Location" => {
-                            mountain.location = get_location(item);
+                            mountain.location = get_location(item, "LocationValue");
+                        }
+                        "Season" => {
+                            mountain.season = Some(get_season(item));
+                        }
+                        "Volcano" => {
+                            mountain.volcano = Some(get_volcano(item));
+                        }
+                        "Alias" => {
+                            mountain.aliases = get_aliases(item);
+                        }
+                        "Parent" => {
+                            let key = String::from("DataValue");
+                            if let Ok(parent_id) = get_value(item, &key, ValueType::String)
+                                .replace("Parent_", "")
+                                .parse::<u32>()
+                            {
+                                mountain.parent = Some(parent_id);
+                            }
                         }
                         _ => {
                             let key = String::from("DataValue");
                             if Some(0) == data_type.find("Area_") {
                                 mountain.area =
                                     get_value(item, &key, ValueType::String).replace("Area_", "");
+                            } else if Some(0) == data_type.find("Range_") {
+                                mountain.range = MountainRangeMapper::find_by_name(
+                                    &get_value(item, &key, ValueType::String).replace("Range_", ""),
+                                );
                             } else if Some(0) == data_type.find("Prefecture_") {
-                                mountain.prefectures.push(
-                                    get_value(item, &key, ValueType::String)
-                                        .replace("Prefecture_", ""),
+                                let prefecture = get_value(item, &key, ValueType::String)
+                                    .replace("Prefecture_", "");
+                                let primary_key = String::from("Primary");
+                                if get_value(item, &primary_key, ValueType::Bool) == "true" {
+                                    mountain.primary_prefecture = Some(prefecture.to_string());
+                                }
+                                mountain.prefectures.push(prefecture);
+                            } else if Some(0) == data_type.find("Park_") {
+                                mountain.parks.push(
+                                    get_value(item, &key, ValueType::String).replace("Park_", ""),
                                 );
                             } else if Some(0) == data_type.find("Tag_") {
                                 mountain.tags.push(
                                     get_value(item, &key, ValueType::String).replace("Tag_", ""),
                                 );
+                            } else if Some(0) == data_type.find("RoadClosure_") {
+                                if let Some(road_closure) = get_road_closure(item) {
+                                    mountain.road_closures.push(road_closure);
+                                }
+                            } else if Some(0) == data_type.find("Access_") {
+                                mountain.access.push(get_access(item));
+                            } else if Some(0) == data_type.find("Route_") {
+                                if let Some(difficulty) = get_difficulty(item) {
+                                    route_difficulties.push(difficulty);
+                                }
+                            } else if Some(0) == data_type.find("SurveyPoint_") {
+                                match get_survey_point(item) {
+                                    Ok(survey_point) => mountain.survey_points.push(survey_point),
+                                    Err(elevation_value) => {
+                                        invalid_elevations.push(elevation_value)
+                                    }
+                                }
                             }
                         }
-                    },
-                    Err(_) => {}
-                },
-                _ => {}
+                    }
+                }
             }
         }
 
+        mountain.difficulty = DifficultySummary::from_difficulties(&route_difficulties);
+        mountain.route_difficulties = route_difficulties;
+
+        // 都道府県ID順に並べ、主たる都道府県を先頭にする
         mountain
+            .prefectures
+            .sort_by_key(|pref| PrefectureMapper::find_id(pref).unwrap_or(u32::MAX));
+        if mountain.primary_prefecture.is_none() {
+            mountain.primary_prefecture = mountain.prefectures.first().cloned();
+        }
+        if let Some(primary) = &mountain.primary_prefecture {
+            if let Some(index) = mountain.prefectures.iter().position(|pref| pref == primary) {
+                let primary_prefecture = mountain.prefectures.remove(index);
+                mountain.prefectures.insert(0, primary_prefecture);
+            }
+        }
+
+        // 主たる測量地点があればその標高を採用する
+        if let Some(primary) = mountain.survey_points.iter().find(|point| point.primary) {
+            elevation = Some(primary.elevation);
+        }
+
+        // 不正な標高を 0 として扱わず、エラーとする
+        match elevation {
+            Some(elevation) if invalid_elevations.is_empty() => mountain.elevation = elevation,
+            _ => {
+                return Err(format!(
+                    "山岳ID {} の標高を取得できません。ElevationValue: {:?}",
+                    mountain.id, invalid_elevations
+                ));
+            }
+        }
+
+        Ok(mountain)
+    }
+}
+
+impl Mountain {
+    /// 地域、都道府県、タグを指定した言語の名称に置き換える
+    ///
+    /// 英語の名称がない場合は日本語の名称のままとする。
+    pub fn localize(&mut self, language: &Language) {
+        if *language == Language::Ja {
+            return;
+        }
+
+        if let Some(area) = AreaMapper::find_english_name(&self.area) {
+            self.area = area.to_string();
+        }
+        self.prefectures = localize_prefectures(&self.prefectures, language);
+        if let Some(primary) = &self.primary_prefecture {
+            if let Some(primary_en) = PrefectureMapper::find_english_name(primary) {
+                self.primary_prefecture = Some(primary_en.to_string());
+            }
+        }
+        self.tags = localize_tags(&self.tags, language);
+
+        if let Some(rank) = &mut self.rank {
+            for group in &mut rank.by_prefecture {
+                if let Some(pref_en) = PrefectureMapper::find_english_name(&group.name) {
+                    group.name = pref_en.to_string();
+                }
+            }
+            for group in &mut rank.by_tag {
+                if let Some(tag_en) = TagMapper::find_english_name(&group.name) {
+                    group.name = tag_en.to_string();
+                }
+            }
+        }
+    }
+}
+
+/// 都道府県名を指定した言語の名称に置き換える
+pub fn localize_prefectures(prefectures: &[String], language: &Language) -> Vec<String> {
+    prefectures
+        .iter()
+        .map(|pref| match language {
+            Language::Ja => pref.to_string(),
+            Language::En => PrefectureMapper::find_english_name(pref)
+                .map(|pref_en| pref_en.to_string())
+                .unwrap_or_else(|| pref.to_string()),
+        })
+        .collect()
+}
+
+/// タグ名を指定した言語の名称に置き換える
+pub fn localize_tags(tags: &[String], language: &Language) -> Vec<String> {
+    tags.iter()
+        .map(|tag| match language {
+            Language::Ja => tag.to_string(),
+            Language::En => TagMapper::find_english_name(tag)
+                .map(|tag_en| tag_en.to_string())
+                .unwrap_or_else(|| tag.to_string()),
+        })
+        .collect()
+}
+
+/// 整数の標高は整数として出力する（例: 3776, 3775.63）
+fn serialize_elevation<S: Serializer>(elevation: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    if elevation.fract() == 0.0 && elevation.abs() < 9_007_199_254_740_992.0 {
+        serializer.serialize_i64(*elevation as i64)
+    } else {
+        serializer.serialize_f64(*elevation)
     }
 }
 
@@ -134,40 +322,285 @@ pub fn get_value(
                 Ok(data_value) => data_value.to_string(),
                 Err(_) => "".to_string(),
             },
+            ValueType::Bool => match attr_value.as_bool() {
+                Ok(data_value) => data_value.to_string(),
+                Err(_) => "".to_string(),
+            },
         },
         _ => "".to_string(),
     }
 }
 
-fn get_location(location_value: &HashMap<String, AttributeValue>) -> Location {
+pub(crate) fn get_location(
+    location_value: &HashMap<String, AttributeValue>,
+    key: &str,
+) -> Location {
     let mut location = Location {
         latitude: 0.0,
         longitude: 0.0,
         gsi_url: "".to_string(),
     };
 
-    match location_value.get(&*"LocationValue") {
-        Some(value_attr) => match value_attr.as_m() {
-            Ok(data_value) => {
-                let lat_key = String::from("Latitude");
-                let lat_value = get_value(data_value, &lat_key, ValueType::Number);
-                if let Ok(latitude) = lat_value.parse::<f64>() {
-                    location.latitude = latitude;
-                }
-
-                let lon_key = String::from("Longitude");
-                let lon_value = get_value(data_value, &lon_key, ValueType::Number);
-                if let Ok(longitude) = lon_value.parse::<f64>() {
-                    location.longitude = longitude;
-                }
+    if let Some(value_attr) = location_value.get(key) {
+        if let Ok(data_value) = value_attr.as_m() {
+            let lat_key = String::from("Latitude");
+            let lat_value = get_value(data_value, &lat_key, ValueType::Number);
+            if let Ok(latitude) = lat_value.parse::<f64>() {
+                location.latitude = latitude;
+            }
 
-                let url_key = String::from("GsiUrl");
-                location.gsi_url = get_value(data_value, &url_key, ValueType::String);
+            let lon_key = String::from("Longitude");
+            let lon_value = get_value(data_value, &lon_key, ValueType::Number);
+            if let Ok(longitude) = lon_value.parse::<f64>() {
+                location.longitude = longitude;
             }
-            Err(_) => {}
-        },
-        _ => {}
+
+            let url_key = String::from("GsiUrl");
+            location.gsi_url = get_value(data_value, &url_key, ValueType::String);
+        }
     }
 
     location
 }
+
+fn get_volcano(volcano_value: &HashMap<String, AttributeValue>) -> Volcano {
+    let active_key = String::from("ActiveVolcano");
+    let monitored_key = String::from("Monitored");
+    let alert_level_key = String::from("AlertLevel");
+
+    let mut alert_level: Option<u8> = None;
+    if let Ok(level) = get_value(volcano_value, &alert_level_key, ValueType::Number).parse::<u8>() {
+        if (1..=MAX_ALERT_LEVEL).contains(&level) {
+            alert_level = Some(level);
+        }
+    }
+
+    Volcano {
+        active: get_value(volcano_value, &active_key, ValueType::Bool) == "true",
+        monitored: get_value(volcano_value, &monitored_key, ValueType::Bool) == "true",
+        alert_level,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        data_type: &str,
+        attributes: &[(&str, AttributeValue)],
+    ) -> HashMap<String, AttributeValue> {
+        let mut item: HashMap<String, AttributeValue> = HashMap::new();
+        item.insert("Id".to_string(), AttributeValue::N("1".to_string()));
+        item.insert(
+            "DataType".to_string(),
+            AttributeValue::S(data_type.to_string()),
+        );
+        for (key, value) in attributes {
+            item.insert(key.to_string(), value.clone());
+        }
+        item
+    }
+
+    fn number(value: &str) -> AttributeValue {
+        AttributeValue::N(value.to_string())
+    }
+
+    fn survey_point(name: &str, elevation: &str, primary: bool) -> HashMap<String, AttributeValue> {
+        item(
+            &format!("SurveyPoint_{}", name),
+            &[
+                ("DataValue", AttributeValue::S(name.to_string())),
+                ("SurveyKind", AttributeValue::S("三角点".to_string())),
+                ("ElevationValue", number(elevation)),
+                ("Primary", AttributeValue::Bool(primary)),
+            ],
+        )
+    }
+
+    fn to_mountain(data: Vec<HashMap<String, AttributeValue>>) -> Result<Mountain, String> {
+        MountainMapper::new(data).to_mountain()
+    }
+
+    #[test]
+    fn decimal_elevation_is_parsed() {
+        let mountain = to_mountain(vec![item(
+            "Elevation",
+            &[("ElevationValue", number("3775.63"))],
+        )])
+        .unwrap();
+        assert_eq!(mountain.elevation, 3775.63);
+    }
+
+    #[test]
+    fn invalid_or_missing_elevation_is_rejected() {
+        // エラーには山岳IDと不正な値を含める
+        assert_eq!(
+            to_mountain(vec![item(
+                "Elevation",
+                &[("ElevationValue", number("abc"))]
+            )]),
+            Err("山岳ID 1 の標高を取得できません。ElevationValue: [\"abc\"]".to_string())
+        );
+        assert!(to_mountain(vec![item(
+            "Elevation",
+            &[("ElevationValue", AttributeValue::S("3776".to_string()))]
+        )])
+        .is_err());
+        assert!(to_mountain(vec![item(
+            "Name",
+            &[("DataValue", AttributeValue::S("富士山".to_string()))]
+        )])
+        .is_err());
+
+        // 測量地点の標高が不正な場合もエラーとする
+        assert_eq!(
+            to_mountain(vec![
+                item("Elevation", &[("ElevationValue", number("3776"))]),
+                survey_point("剣ヶ峰", "x", false),
+            ]),
+            Err("山岳ID 1 の標高を取得できません。ElevationValue: [\"x\"]".to_string())
+        );
+    }
+
+    #[test]
+    fn primary_survey_point_overrides_elevation() {
+        let mountain = to_mountain(vec![
+            item("Elevation", &[("ElevationValue", number("3776"))]),
+            survey_point("三角点", "3775.51", false),
+            survey_point("最高点", "3775.63", true),
+        ])
+        .unwrap();
+        assert_eq!(mountain.elevation, 3775.63);
+        assert_eq!(mountain.survey_points.len(), 2);
+        assert_eq!(mountain.survey_points[0].name, "三角点");
+        assert_eq!(mountain.survey_points[0].elevation, 3775.51);
+        assert!(!mountain.survey_points[0].primary);
+
+        // 主たる地点がなければ Elevation の値を用いる
+        let mountain = to_mountain(vec![
+            item("Elevation", &[("ElevationValue", number("3776"))]),
+            survey_point("三角点", "3775.51", false),
+        ])
+        .unwrap();
+        assert_eq!(mountain.elevation, 3776.0);
+
+        // Elevation がなくても主たる地点があればその標高とする
+        let mountain = to_mountain(vec![survey_point("最高点", "3775.63", true)]).unwrap();
+        assert_eq!(mountain.elevation, 3775.63);
+    }
+
+    fn prefecture(name: &str, primary: Option<bool>) -> HashMap<String, AttributeValue> {
+        let data_value = format!("Prefecture_{}", name);
+        let mut attributes = vec![("DataValue", AttributeValue::S(data_value.to_string()))];
+        if let Some(primary) = primary {
+            attributes.push(("Primary", AttributeValue::Bool(primary)));
+        }
+        item(&data_value, &attributes)
+    }
+
+    fn prefectures_of(
+        prefectures: Vec<HashMap<String, AttributeValue>>,
+    ) -> (Vec<String>, Option<String>) {
+        let mut data = vec![item("Elevation", &[("ElevationValue", number("3000"))])];
+        data.extend(prefectures);
+        let mountain = to_mountain(data).unwrap();
+        (mountain.prefectures, mountain.primary_prefecture)
+    }
+
+    #[test]
+    fn primary_prefecture_comes_first() {
+        let (prefectures, primary) = prefectures_of(vec![
+            prefecture("岐阜県", Some(false)),
+            prefecture("富山県", Some(false)),
+            prefecture("長野県", Some(true)),
+        ]);
+        // 主たる都道府県の後は都道府県ID順
+        assert_eq!(prefectures, vec!["長野県", "富山県", "岐阜県"]);
+        assert_eq!(primary, Some("長野県".to_string()));
+    }
+
+    #[test]
+    fn first_prefecture_by_id_is_primary_without_flag() {
+        let (prefectures, primary) = prefectures_of(vec![
+            prefecture("静岡県", None),
+            prefecture("山梨県", Some(false)),
+        ]);
+        assert_eq!(prefectures, vec!["山梨県", "静岡県"]);
+        assert_eq!(primary, Some("山梨県".to_string()));
+
+        // 都道府県がない場合は主たる都道府県もない
+        assert_eq!(prefectures_of(vec![]), (vec![], None));
+    }
+
+    #[test]
+    fn unknown_prefecture_is_sorted_last() {
+        let (prefectures, primary) =
+            prefectures_of(vec![prefecture("不明", None), prefecture("北海道", None)]);
+        assert_eq!(prefectures, vec!["北海道", "不明"]);
+        assert_eq!(primary, Some("北海道".to_string()));
+    }
+
+    #[test]
+    fn volcano_is_mapped_from_item() {
+        let volcano = get_volcano(&item(
+            "Volcano",
+            &[
+                ("ActiveVolcano", AttributeValue::Bool(true)),
+                ("Monitored", AttributeValue::Bool(true)),
+                ("AlertLevel", number("3")),
+            ],
+        ));
+        assert_eq!(
+            volcano,
+            Volcano {
+                active: true,
+                monitored: true,
+                alert_level: Some(3),
+            }
+        );
+
+        // 属性がない場合は活火山でなく、噴火警戒レベルもない
+        let volcano = get_volcano(&item("Volcano", &[]));
+        assert_eq!(
+            volcano,
+            Volcano {
+                active: false,
+                monitored: false,
+                alert_level: None,
+            }
+        );
+    }
+
+    #[test]
+    fn alert_level_out_of_range_is_ignored() {
+        for level in ["0", "6", "-1", "x"] {
+            let volcano = get_volcano(&item(
+                "Volcano",
+                &[
+                    ("ActiveVolcano", AttributeValue::Bool(true)),
+                    ("AlertLevel", number(level)),
+                ],
+            ));
+            assert_eq!(volcano.alert_level, None, "AlertLevel: {}", level);
+        }
+
+        let volcano = get_volcano(&item("Volcano", &[("AlertLevel", number("5"))]));
+        assert_eq!(volcano.alert_level, Some(MAX_ALERT_LEVEL));
+    }
+
+    #[test]
+    fn integer_elevation_is_serialized_as_integer() {
+        let mut mountain = Mountain {
+            elevation: 3776.0,
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(&mountain).unwrap()["elevation"], 3776);
+
+        mountain.elevation = 3775.63;
+        assert_eq!(
+            serde_json::to_value(&mountain).unwrap()["elevation"],
+            3775.63
+        );
+    }
+}
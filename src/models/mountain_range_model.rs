@@ -0,0 +1,166 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub trait MountainRangeBaseMapper {
+    fn new(key: u32) -> Self;
+    #[allow(clippy::result_unit_err)]
+    fn to_mountain_range(&self) -> Result<String, ()>;
+}
+
+pub struct MountainRangeMapper {
+    key: u32,
+}
+
+/// 山脈・山地
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MountainRange {
+    pub(crate) id: u32,
+    pub(crate) name: String,
+}
+
+struct MountainRangeData {
+    id: u32,
+    name: &'static str,
+}
+
+impl MountainRangeBaseMapper for MountainRangeMapper {
+    fn new(key: u32) -> Self {
+        Self { key }
+    }
+
+    fn to_mountain_range(&self) -> Result<String, ()> {
+        let prefix = "Range_".to_string();
+
+        for range in MountainRangeMapper::RANGES {
+            if range.id == self.key {
+                return Ok(format!("{}{}", prefix, range.name));
+            }
+        }
+
+        Err(())
+    }
+}
+
+impl MountainRangeMapper {
+    /// 登録されている全ての山脈・山地
+    pub fn all() -> Vec<MountainRange> {
+        MountainRangeMapper::RANGES
+            .iter()
+            .map(|range| MountainRange {
+                id: range.id,
+                name: range.name.to_string(),
+            })
+            .collect()
+    }
+
+    /// 山脈・山地名から山脈・山地を取得する
+    pub fn find_by_name(name: &str) -> Option<MountainRange> {
+        MountainRangeMapper::RANGES
+            .iter()
+            .find(|range| range.name == name)
+            .map(|range| MountainRange {
+                id: range.id,
+                name: range.name.to_string(),
+            })
+    }
+
+    const RANGES: [MountainRangeData; 24] = [
+        MountainRangeData {
+            id: 1,
+            name: "日高山脈",
+        },
+        MountainRangeData {
+            id: 2,
+            name: "石狩山地",
+        },
+        MountainRangeData {
+            id: 3,
+            name: "夕張山地",
+        },
+        MountainRangeData {
+            id: 4,
+            name: "天塩山地",
+        },
+        MountainRangeData {
+            id: 5,
+            name: "奥羽山脈",
+        },
+        MountainRangeData {
+            id: 6,
+            name: "出羽山地",
+        },
+        MountainRangeData {
+            id: 7,
+            name: "北上高地",
+        },
+        MountainRangeData {
+            id: 8,
+            name: "阿武隈高地",
+        },
+        MountainRangeData {
+            id: 9,
+            name: "朝日山地",
+        },
+        MountainRangeData {
+            id: 10,
+            name: "飯豊山地",
+        },
+        MountainRangeData {
+            id: 11,
+            name: "越後山脈",
+        },
+        MountainRangeData {
+            id: 12,
+            name: "三国山脈",
+        },
+        MountainRangeData {
+            id: 13,
+            name: "関東山地",
+        },
+        MountainRangeData {
+            id: 14,
+            name: "丹沢山地",
+        },
+        MountainRangeData {
+            id: 15,
+            name: "飛騨山脈",
+        },
+        MountainRangeData {
+            id: 16,
+            name: "木曽山脈",
+        },
+        MountainRangeData {
+            id: 17,
+            name: "赤石山脈",
+        },
+        MountainRangeData {
+            id: 18,
+            name: "両白山地",
+        },
+        MountainRangeData {
+            id: 19,
+            name: "鈴鹿山脈",
+        },
+        MountainRangeData {
+            id: 20,
+            name: "紀伊山地",
+        },
+        MountainRangeData {
+            id: 21,
+            name: "中国山地",
+        },
+        MountainRangeData {
+            id: 22,
+            name: "四国山地",
+        },
+        MountainRangeData {
+            id: 23,
+            name: "九州山地",
+        },
+        MountainRangeData {
+            id: 24,
+            name: "八ヶ岳連峰",
+        },
+    ];
+}
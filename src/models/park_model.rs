@@ -0,0 +1,233 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub trait ParkBaseMapper {
+    fn new(key: u32) -> Self;
+    #[allow(clippy::result_unit_err)]
+    fn to_park(&self) -> Result<String, ()>;
+}
+
+pub struct ParkMapper {
+    key: u32,
+}
+
+/// 国立公園
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Park {
+    pub(crate) id: u32,
+    pub(crate) name: String,
+}
+
+struct ParkData {
+    id: u32,
+    name: &'static str,
+}
+
+impl ParkBaseMapper for ParkMapper {
+    fn new(key: u32) -> Self {
+        Self { key }
+    }
+
+    fn to_park(&self) -> Result<String, ()> {
+        let prefix = "Park_".to_string();
+
+        for park in ParkMapper::PARKS {
+            if park.id == self.key {
+                return Ok(format!("{}{}", prefix, park.name));
+            }
+        }
+
+        Err(())
+    }
+}
+
+impl ParkMapper {
+    /// 登録されている全ての国立公園
+    pub fn all() -> Vec<Park> {
+        ParkMapper::PARKS
+            .iter()
+            .map(|park| Park {
+                id: park.id,
+                name: park.name.to_string(),
+            })
+            .collect()
+    }
+
+    const PARKS: [ParkData; 35] = [
+        ParkData {
+            id: 1,
+            name: "利尻礼文サロベツ国立公園",
+        },
+        ParkData {
+            id: 2,
+            name: "知床国立公園",
+        },
+        ParkData {
+            id: 3,
+            name: "阿寒摩周国立公園",
+        },
+        ParkData {
+            id: 4,
+            name: "釧路湿原国立公園",
+        },
+        ParkData {
+            id: 5,
+            name: "大雪山国立公園",
+        },
+        ParkData {
+            id: 6,
+            name: "支笏洞爺国立公園",
+        },
+        ParkData {
+            id: 7,
+            name: "日高山脈襟裳十勝国立公園",
+        },
+        ParkData {
+            id: 8,
+            name: "十和田八幡平国立公園",
+        },
+        ParkData {
+            id: 9,
+            name: "三陸復興国立公園",
+        },
+        ParkData {
+            id: 10,
+            name: "磐梯朝日国立公園",
+        },
+        ParkData {
+            id: 11,
+            name: "日光国立公園",
+        },
+        ParkData {
+            id: 12,
+            name: "尾瀬国立公園",
+        },
+        ParkData {
+            id: 13,
+            name: "上信越高原国立公園",
+        },
+        ParkData {
+            id: 14,
+            name: "妙高戸隠連山国立公園",
+        },
+        ParkData {
+            id: 15,
+            name: "秩父多摩甲斐国立公園",
+        },
+        ParkData {
+            id: 16,
+            name: "小笠原国立公園",
+        },
+        ParkData {
+            id: 17,
+            name: "富士箱根伊豆国立公園",
+        },
+        ParkData {
+            id: 18,
+            name: "中部山岳国立公園",
+        },
+        ParkData {
+            id: 19,
+            name: "白山国立公園",
+        },
+        ParkData {
+            id: 20,
+            name: "南アルプス国立公園",
+        },
+        ParkData {
+            id: 21,
+            name: "伊勢志摩国立公園",
+        },
+        ParkData {
+            id: 22,
+            name: "吉野熊野国立公園",
+        },
+        ParkData {
+            id: 23,
+            name: "山陰海岸国立公園",
+        },
+        ParkData {
+            id: 24,
+            name: "瀬戸内海国立公園",
+        },
+        ParkData {
+            id: 25,
+            name: "大山隠岐国立公園",
+        },
+        ParkData {
+            id: 26,
+            name: "足摺宇和海国立公園",
+        },
+        ParkData {
+            id: 27,
+            name: "西海国立公園",
+        },
+        ParkData {
+            id: 28,
+            name: "雲仙天草国立公園",
+        },
+        ParkData {
+            id: 29,
+            name: "阿蘇くじゅう国立公園",
+        },
+        ParkData {
+            id: 30,
+            name: "霧島錦江湾国立公園",
+        },
+        ParkData {
+            id: 31,
+            name: "屋久島国立公園",
+        },
+        ParkData {
+            id: 32,
+            name: "奄美群島国立公園",
+        },
+        ParkData {
+            id: 33,
+            name: "やんばる国立公園",
+        },
+        ParkData {
+            id: 34,
+            name: "慶良間諸島国立公園",
+        },
+        ParkData {
+            id: 35,
+            name: "西表石垣国立公園",
+        },
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn park_id_is_mapped_to_data_value() {
+        assert_eq!(
+            ParkMapper::new(5).to_park(),
+            Ok("Park_大雪山国立公園".to_string())
+        );
+        assert_eq!(
+            ParkMapper::new(35)
+                .to_park()
+                .map(|park| park.starts_with("Park_")),
+            Ok(true)
+        );
+        assert_eq!(ParkMapper::new(0).to_park(), Err(()));
+        assert_eq!(ParkMapper::new(36).to_park(), Err(()));
+    }
+
+    #[test]
+    fn all_parks_are_listed_in_id_order() {
+        let parks = ParkMapper::all();
+        let ids: Vec<u32> = parks.iter().map(|park| park.id).collect();
+        assert_eq!(ids, (1..=35).collect::<Vec<u32>>());
+
+        let mut names: Vec<&str> = parks.iter().map(|park| park.name.as_str()).collect();
+        assert!(names.iter().all(|name| name.ends_with("国立公園")));
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), parks.len());
+    }
+}
@@ -1,5 +1,6 @@
 pub trait PrefectureBaseMapper {
     fn new(key: u32) -> Self;
+    #[allow(clippy::result_unit_err)]
     fn to_prefecture(&self) -> Result<String, ()>;
 }
 
@@ -10,6 +11,7 @@ pub struct PrefectureMapper {
 struct Prefecture {
     id: u32,
     name: &'static str,
+    name_en: &'static str,
 }
 
 impl PrefectureBaseMapper for PrefectureMapper {
@@ -22,7 +24,7 @@ impl PrefectureBaseMapper for PrefectureMapper {
 
         for pref in PrefectureMapper::PREFECTURES {
             if pref.id == self.key {
-                return Ok(format!("{}{}", prefix, pref.name.to_string()));
+                return Ok(format!("{}{}", prefix, pref.name));
             }
         }
 
@@ -31,194 +33,257 @@ impl PrefectureBaseMapper for PrefectureMapper {
 }
 
 impl PrefectureMapper {
+    /// 都道府県名から都道府県ID（JIS X 0401）を取得する
+    pub fn find_id(name: &str) -> Option<u32> {
+        PrefectureMapper::PREFECTURES
+            .iter()
+            .find(|pref| pref.name == name)
+            .map(|pref| pref.id)
+    }
+
+    /// 都道府県名から英語の都道府県名を取得する
+    pub fn find_english_name(name: &str) -> Option<&'static str> {
+        PrefectureMapper::PREFECTURES
+            .iter()
+            .find(|pref| pref.name == name)
+            .map(|pref| pref.name_en)
+    }
+
     const PREFECTURES: [Prefecture; 47] = [
         Prefecture {
             id: 1,
             name: "北海道",
+            name_en: "Hokkaido",
         },
         Prefecture {
             id: 2,
             name: "青森県",
+            name_en: "Aomori",
         },
         Prefecture {
             id: 3,
             name: "岩手県",
+            name_en: "Iwate",
         },
         Prefecture {
             id: 4,
             name: "宮城県",
+            name_en: "Miyagi",
         },
         Prefecture {
             id: 5,
             name: "秋田県",
+            name_en: "Akita",
         },
         Prefecture {
             id: 6,
             name: "山形県",
+            name_en: "Yamagata",
         },
         Prefecture {
             id: 7,
             name: "福島県",
+            name_en: "Fukushima",
         },
         Prefecture {
             id: 8,
             name: "茨城県",
+            name_en: "Ibaraki",
         },
         Prefecture {
             id: 9,
             name: "栃木県",
+            name_en: "Tochigi",
         },
         Prefecture {
             id: 10,
             name: "群馬県",
+            name_en: "Gunma",
         },
         Prefecture {
             id: 11,
             name: "埼玉県",
+            name_en: "Saitama",
         },
         Prefecture {
             id: 12,
             name: "千葉県",
+            name_en: "Chiba",
         },
         Prefecture {
             id: 13,
             name: "東京都",
+            name_en: "Tokyo",
         },
         Prefecture {
             id: 14,
             name: "神奈川県",
+            name_en: "Kanagawa",
         },
         Prefecture {
             id: 15,
             name: "新潟県",
+            name_en: "Niigata",
         },
         Prefecture {
             id: 16,
             name: "富山県",
+            name_en: "Toyama",
         },
         Prefecture {
             id: 17,
             name: "石川県",
+            name_en: "Ishikawa",
         },
         Prefecture {
             id: 18,
             name: "福井県",
+            name_en: "Fukui",
         },
         Prefecture {
             id: 19,
             name: "山梨県",
+            name_en: "Yamanashi",
         },
         Prefecture {
             id: 20,
             name: "長野県",
+            name_en: "Nagano",
         },
         Prefecture {
             id: 21,
             name: "岐阜県",
+            name_en: "Gifu",
         },
         Prefecture {
             id: 22,
             name: "静岡県",
+            name_en: "Shizuoka",
         },
         Prefecture {
             id: 23,
             name: "愛知県",
+            name_en: "Aichi",
         },
         Prefecture {
             id: 24,
             name: "三重県",
+            name_en: "Mie",
         },
         Prefecture {
             id: 25,
             name: "滋賀県",
+            name_en: "Shiga",
         },
         Prefecture {
             id: 26,
             name: "京都府",
+            name_en: "Kyoto",
         },
         Prefecture {
             id: 27,
             name: "大阪府",
+            name_en: "Osaka",
         },
         Prefecture {
             id: 28,
             name: "兵庫県",
+            name_en: "Hyogo",
         },
         Prefecture {
             id: 29,
             name: "奈良県",
+            name_en: "Nara",
         },
         Prefecture {
             id: 30,
             name: "和歌山県",
+            name_en: "Wakayama",
         },
         Prefecture {
             id: 31,
             name: "鳥取県",
+            name_en: "Tottori",
         },
         Prefecture {
             id: 32,
             name: "島根県",
+            name_en: "Shimane",
         },
         Prefecture {
             id: 33,
             name: "岡山県",
+            name_en: "Okayama",
         },
         Prefecture {
             id: 34,
             name: "広島県",
+            name_en: "Hiroshima",
         },
         Prefecture {
             id: 35,
             name: "山口県",
+            name_en: "Yamaguchi",
         },
         Prefecture {
             id: 36,
             name: "徳島県",
+            name_en: "Tokushima",
         },
         Prefecture {
             id: 37,
             name: "香川県",
+            name_en: "Kagawa",
         },
         Prefecture {
             id: 38,
             name: "愛媛県",
+            name_en: "Ehime",
         },
         Prefecture {
             id: 39,
             name: "高知県",
+            name_en: "Kochi",
         },
         Prefecture {
             id: 40,
             name: "福岡県",
+            name_en: "Fukuoka",
         },
         Prefecture {
             id: 41,
             name: "佐賀県",
+            name_en: "Saga",
         },
         Prefecture {
             id: 42,
             name: "長崎県",
+            name_en: "Nagasaki",
         },
         Prefecture {
             id: 43,
             name: "熊本県",
+            name_en: "Kumamoto",
         },
         Prefecture {
             id: 44,
             name: "大分県",
+            name_en: "Oita",
         },
         Prefecture {
             id: 45,
             name: "宮崎県",
+            name_en: "Miyazaki",
         },
         Prefecture {
             id: 46,
             name: "鹿児島県",
+            name_en: "Kagoshima",
         },
         Prefecture {
             id: 47,
             name: "沖縄県",
+            name_en: "Okinawa",
         },
     ];
 }
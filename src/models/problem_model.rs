@@ -0,0 +1,236 @@
+use crate::i18n::{Language, Message};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 問題の種類を識別する URI の接頭辞
+const PROBLEM_TYPE_BASE_URI: &str = "https://mountix-docs.codemountains.org/problems/";
+
+/// RFC 7807 の問題の種類
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ProblemType {
+    InvalidParameter,
+    NotFound,
+    NotAcceptable,
+    InternalError,
+}
+
+impl ProblemType {
+    pub fn status(&self) -> u16 {
+        match self {
+            ProblemType::InvalidParameter => 400,
+            ProblemType::NotFound => 404,
+            ProblemType::NotAcceptable => 406,
+            ProblemType::InternalError => 500,
+        }
+    }
+
+    pub fn uri(&self) -> String {
+        let name = match self {
+            ProblemType::InvalidParameter => "invalid-parameter",
+            ProblemType::NotFound => "not-found",
+            ProblemType::NotAcceptable => "not-acceptable",
+            ProblemType::InternalError => "internal-error",
+        };
+        format!("{}{}", PROBLEM_TYPE_BASE_URI, name)
+    }
+
+    fn title(&self) -> Message {
+        match self {
+            ProblemType::InvalidParameter => Message::InvalidParameterTitle,
+            ProblemType::NotFound => Message::NotFoundTitle,
+            ProblemType::NotAcceptable => Message::NotAcceptableTitle,
+            ProblemType::InternalError => Message::InternalErrorTitle,
+        }
+    }
+}
+
+/// クエリパラメータの検証エラー
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParameterError {
+    pub parameter: String,
+    pub message: Message,
+}
+
+impl ParameterError {
+    pub fn new(parameter: &str, message: Message) -> Self {
+        Self {
+            parameter: parameter.to_string(),
+            message,
+        }
+    }
+}
+
+/// application/problem+json のレスポンス（RFC 7807）
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub instance: Option<String>,
+    /// 不正なクエリパラメータごとのエラー
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub errors: Vec<ProblemError>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemError {
+    pub parameter: String,
+    pub detail: String,
+}
+
+impl Problem {
+    pub fn new(problem_type: ProblemType, detail: Message, language: &Language) -> Self {
+        Self {
+            problem_type: problem_type.uri(),
+            title: problem_type.title().text(language),
+            status: problem_type.status(),
+            detail: detail.text(language),
+            instance: None,
+            errors: vec![],
+        }
+    }
+
+    /// クエリパラメータの検証エラーから 400 の問題を作成する
+    pub fn invalid_parameters(errors: &[ParameterError], language: &Language) -> Self {
+        let mut problem = Problem::new(
+            ProblemType::InvalidParameter,
+            Message::InvalidParameterDetail,
+            language,
+        );
+        problem.errors = errors
+            .iter()
+            .map(|error| ProblemError {
+                parameter: error.parameter.to_string(),
+                detail: error.message.text(language),
+            })
+            .collect();
+        problem
+    }
+
+    pub fn internal_error(language: &Language) -> Self {
+        Problem::new(ProblemType::InternalError, Message::InternalError, language)
+    }
+
+    /// 問題が発生したリクエストのパスを instance に設定する
+    pub fn with_instance(mut self, uri_path: &str) -> Self {
+        self.instance = Some(uri_path.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn problem_type_status_and_uri() {
+        let cases = [
+            (ProblemType::InvalidParameter, 400, "invalid-parameter"),
+            (ProblemType::NotFound, 404, "not-found"),
+            (ProblemType::NotAcceptable, 406, "not-acceptable"),
+            (ProblemType::InternalError, 500, "internal-error"),
+        ];
+        for (problem_type, status, name) in cases {
+            assert_eq!(problem_type.status(), status);
+            assert_eq!(
+                problem_type.uri(),
+                format!("https://mountix-docs.codemountains.org/problems/{}", name)
+            );
+        }
+    }
+
+    #[test]
+    fn new_uses_localized_title_and_detail() {
+        let problem = Problem::new(
+            ProblemType::NotFound,
+            Message::MountainNotFound,
+            &Language::En,
+        );
+        assert_eq!(problem.title, "Resource not found.");
+        assert_eq!(problem.detail, "The mountain was not found.");
+        assert_eq!(problem.status, 404);
+        assert_eq!(problem.instance, None);
+        assert!(problem.errors.is_empty());
+
+        let problem = Problem::internal_error(&Language::Ja);
+        assert_eq!(problem.status, 500);
+        assert_eq!(problem.detail, Message::InternalError.text(&Language::Ja));
+    }
+
+    #[test]
+    fn invalid_parameters_keeps_each_error() {
+        let errors = vec![
+            ParameterError::new("limit", Message::InvalidLimit),
+            ParameterError::new("sort", Message::InvalidSort),
+        ];
+        let problem = Problem::invalid_parameters(&errors, &Language::En);
+
+        assert_eq!(problem.status, 400);
+        assert_eq!(problem.problem_type, ProblemType::InvalidParameter.uri());
+        assert_eq!(
+            problem.errors,
+            vec![
+                ProblemError {
+                    parameter: "limit".to_string(),
+                    detail: "limit must be an integer of 1 or more.".to_string(),
+                },
+                ProblemError {
+                    parameter: "sort".to_string(),
+                    detail: "Invalid sort.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn with_instance_sets_request_path() {
+        let problem = Problem::internal_error(&Language::Ja).with_instance("/mountains/1");
+        assert_eq!(problem.instance, Some("/mountains/1".to_string()));
+    }
+
+    #[test]
+    fn serializes_as_problem_json() {
+        let errors = vec![ParameterError::new("offset", Message::InvalidOffset)];
+        let problem =
+            Problem::invalid_parameters(&errors, &Language::En).with_instance("/mountains");
+
+        assert_eq!(
+            serde_json::to_value(&problem).unwrap(),
+            json!({
+                "type": "https://mountix-docs.codemountains.org/problems/invalid-parameter",
+                "title": "Invalid parameter.",
+                "status": 400,
+                "detail": "One or more query parameters are invalid. See errors for details.",
+                "instance": "/mountains",
+                "errors": [
+                    {
+                        "parameter": "offset",
+                        "detail": "offset must be an integer of 0 or more."
+                    }
+                ]
+            })
+        );
+
+        // instance、errors が無い場合は出力しない
+        let problem = Problem::new(
+            ProblemType::NotAcceptable,
+            Message::NotAcceptable,
+            &Language::En,
+        );
+        assert_eq!(
+            serde_json::to_value(&problem).unwrap(),
+            json!({
+                "type": "https://mountix-docs.codemountains.org/problems/not-acceptable",
+                "title": "Not acceptable.",
+                "status": 406,
+                "detail": "The requested format is not supported."
+            })
+        );
+    }
+}
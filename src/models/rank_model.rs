@@ -0,0 +1,26 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 標高順位（同じ標高の山岳は同順位とし、次の順位は人数分繰り下げる）
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Rank {
+    pub national: RankValue,
+    pub by_prefecture: Vec<GroupRank>,
+    pub by_tag: Vec<GroupRank>,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RankValue {
+    pub rank: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRank {
+    pub name: String,
+    pub rank: usize,
+    pub total: usize,
+}
@@ -0,0 +1,93 @@
+use crate::models::{get_difficulty, get_location, get_value, Difficulty, Location, ValueType};
+use aws_sdk_dynamodb::model::AttributeValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub trait RouteBaseMapper {
+    fn new(data: HashMap<String, AttributeValue>) -> Self;
+    fn to_route(&self) -> Route;
+}
+
+pub struct RouteMapper {
+    data: HashMap<String, AttributeValue>,
+}
+
+/// 登山ルート
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Route {
+    pub(crate) id: u32,
+    pub(crate) mountain_id: u32,
+    pub(crate) name: String,
+    pub(crate) trailhead: Trailhead,
+    /// 距離（km）
+    pub(crate) distance: f64,
+    /// 累積標高差（m）
+    pub(crate) elevation_gain: u32,
+    /// 標準コースタイム（分）
+    pub(crate) course_time: u32,
+    pub(crate) difficulty: Option<Difficulty>,
+}
+
+/// 登山口
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Trailhead {
+    pub(crate) name: String,
+    pub(crate) location: Location,
+}
+
+impl RouteBaseMapper for RouteMapper {
+    fn new(data: HashMap<String, AttributeValue>) -> Self {
+        Self { data }
+    }
+
+    fn to_route(&self) -> Route {
+        let item = &self.data;
+
+        let mut route = Route {
+            id: 0,
+            mountain_id: 0,
+            name: get_value(item, &String::from("RouteName"), ValueType::String),
+            trailhead: Trailhead {
+                name: get_value(item, &String::from("TrailheadName"), ValueType::String),
+                location: get_location(item, "TrailheadLocation"),
+            },
+            distance: 0.0,
+            elevation_gain: 0,
+            course_time: 0,
+            difficulty: get_difficulty(item),
+        };
+
+        let key = String::from("Id");
+        if let Ok(mountain_id) = get_value(item, &key, ValueType::Number).parse::<u32>() {
+            route.mountain_id = mountain_id;
+        }
+
+        let key = String::from("DataValue");
+        if let Ok(id) = get_value(item, &key, ValueType::String)
+            .replace("Route_", "")
+            .parse::<u32>()
+        {
+            route.id = id;
+        }
+
+        let key = String::from("Distance");
+        if let Ok(distance) = get_value(item, &key, ValueType::Number).parse::<f64>() {
+            route.distance = distance;
+        }
+
+        let key = String::from("ElevationGain");
+        if let Ok(elevation_gain) = get_value(item, &key, ValueType::Number).parse::<u32>() {
+            route.elevation_gain = elevation_gain;
+        }
+
+        let key = String::from("CourseTime");
+        if let Ok(course_time) = get_value(item, &key, ValueType::Number).parse::<u32>() {
+            route.course_time = course_time;
+        }
+
+        route
+    }
+}
@@ -0,0 +1,176 @@
+use crate::models::{get_value, ValueType};
+use aws_sdk_dynamodb::model::AttributeValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 登山適期
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Season {
+    /// 推奨月（1〜12）
+    pub(crate) months: Vec<u32>,
+    /// 積雪期の注意事項
+    pub(crate) snow_note: String,
+}
+
+/// 道路の通行止め期間（MM-DD 形式、from が to より後の場合は年をまたぐ期間）
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoadClosure {
+    pub(crate) road: String,
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) note: String,
+}
+
+/// 最寄りの駅・バス停
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Access {
+    pub(crate) name: String,
+    /// station または busStop
+    pub(crate) kind: String,
+    pub(crate) note: String,
+}
+
+pub(crate) fn get_season(season_value: &HashMap<String, AttributeValue>) -> Season {
+    let mut months: Vec<u32> = Vec::new();
+    if let Some(value_attr) = season_value.get("SeasonMonths") {
+        if let Ok(month_values) = value_attr.as_ns() {
+            for month_value in month_values {
+                if let Ok(month) = month_value.parse::<u32>() {
+                    if (1..=12).contains(&month) {
+                        months.push(month);
+                    }
+                }
+            }
+        }
+    }
+    months.sort_unstable();
+    months.dedup();
+
+    let note_key = String::from("SnowNote");
+
+    Season {
+        months,
+        snow_note: get_value(season_value, &note_key, ValueType::String),
+    }
+}
+
+/// 期間が MM-DD 形式の日付でない通行止めは None とする
+pub(crate) fn get_road_closure(
+    closure_value: &HashMap<String, AttributeValue>,
+) -> Option<RoadClosure> {
+    let road_key = String::from("DataValue");
+    let from_key = String::from("ClosureFrom");
+    let to_key = String::from("ClosureTo");
+    let note_key = String::from("ClosureNote");
+
+    let from = get_value(closure_value, &from_key, ValueType::String);
+    let to = get_value(closure_value, &to_key, ValueType::String);
+    if !is_month_day(&from) || !is_month_day(&to) {
+        return None;
+    }
+
+    Some(RoadClosure {
+        road: get_value(closure_value, &road_key, ValueType::String),
+        from,
+        to,
+        note: get_value(closure_value, &note_key, ValueType::String),
+    })
+}
+
+/// MM-DD 形式で、月日が存在するか（年を持たないため 02-29 は有効とする）
+fn is_month_day(value: &str) -> bool {
+    let (month, day) = match value.split_once('-') {
+        Some(month_day) => month_day,
+        None => return false,
+    };
+    let is_two_digits = |part: &str| part.len() == 2 && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_two_digits(month) || !is_two_digits(day) {
+        return false;
+    }
+
+    let last_day = match month.parse::<u32>() {
+        Ok(2) => 29,
+        Ok(4 | 6 | 9 | 11) => 30,
+        Ok(1..=12) => 31,
+        _ => return false,
+    };
+    matches!(day.parse::<u32>(), Ok(day) if (1..=last_day).contains(&day))
+}
+
+pub(crate) fn get_access(access_value: &HashMap<String, AttributeValue>) -> Access {
+    let name_key = String::from("DataValue");
+    let kind_key = String::from("AccessKind");
+    let note_key = String::from("AccessNote");
+
+    Access {
+        name: get_value(access_value, &name_key, ValueType::String),
+        kind: get_value(access_value, &kind_key, ValueType::String),
+        note: get_value(access_value, &note_key, ValueType::String),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closure_item(from: &str, to: &str) -> HashMap<String, AttributeValue> {
+        let mut item: HashMap<String, AttributeValue> = HashMap::new();
+        item.insert(
+            "DataValue".to_string(),
+            AttributeValue::S("富士スバルライン".to_string()),
+        );
+        item.insert(
+            "ClosureFrom".to_string(),
+            AttributeValue::S(from.to_string()),
+        );
+        item.insert("ClosureTo".to_string(), AttributeValue::S(to.to_string()));
+        item
+    }
+
+    #[test]
+    fn valid_month_days() {
+        assert!(is_month_day("01-01"));
+        assert!(is_month_day("12-31"));
+        assert!(is_month_day("02-29"));
+        assert!(is_month_day("04-30"));
+    }
+
+    #[test]
+    fn invalid_month_days() {
+        assert!(!is_month_day("13-40"));
+        assert!(!is_month_day("00-10"));
+        assert!(!is_month_day("04-31"));
+        assert!(!is_month_day("02-30"));
+        assert!(!is_month_day("12-00"));
+        assert!(!is_month_day("1-05"));
+        assert!(!is_month_day("+1-05"));
+        assert!(!is_month_day("0105"));
+        assert!(!is_month_day("01-05-01"));
+        assert!(!is_month_day(""));
+    }
+
+    #[test]
+    fn road_closure_may_wrap_past_year_end() {
+        let closure = get_road_closure(&closure_item("11-15", "04-20")).unwrap();
+        assert_eq!(closure.road, "富士スバルライン");
+        assert_eq!(closure.from, "11-15");
+        assert_eq!(closure.to, "04-20");
+
+        let closure = get_road_closure(&closure_item("12-31", "01-01")).unwrap();
+        assert_eq!(
+            (closure.from.as_str(), closure.to.as_str()),
+            ("12-31", "01-01")
+        );
+    }
+
+    #[test]
+    fn road_closure_with_invalid_dates_is_ignored() {
+        assert_eq!(get_road_closure(&closure_item("13-40", "04-20")), None);
+        assert_eq!(get_road_closure(&closure_item("11-15", "")), None);
+        assert_eq!(get_road_closure(&HashMap::new()), None);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::models::{get_value, ValueType};
+use aws_sdk_dynamodb::model::AttributeValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 標高の測量地点（三角点、最高点など）
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SurveyPoint {
+    pub(crate) name: String,
+    pub(crate) kind: String,
+    pub(crate) elevation: f64,
+    /// 山岳の標高として採用する地点
+    pub(crate) primary: bool,
+}
+
+/// 測量地点を取得する（標高が不正な場合はその値をエラーとして返す）
+pub(crate) fn get_survey_point(
+    point_value: &HashMap<String, AttributeValue>,
+) -> Result<SurveyPoint, String> {
+    let name_key = String::from("DataValue");
+    let kind_key = String::from("SurveyKind");
+    let elevation_key = String::from("ElevationValue");
+    let primary_key = String::from("Primary");
+
+    let elevation_value = get_value(point_value, &elevation_key, ValueType::Number);
+    let elevation = match elevation_value.parse::<f64>() {
+        Ok(elevation) => elevation,
+        Err(_) => return Err(elevation_value),
+    };
+
+    Ok(SurveyPoint {
+        name: get_value(point_value, &name_key, ValueType::String),
+        kind: get_value(point_value, &kind_key, ValueType::String),
+        elevation,
+        primary: get_value(point_value, &primary_key, ValueType::Bool) == "true",
+    })
+}
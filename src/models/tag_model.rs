@@ -1,5 +1,6 @@
 pub trait TagBaseMapper {
     fn new(key: u32) -> Self;
+    #[allow(clippy::result_unit_err)]
     fn to_tag(&self) -> Result<String, ()>;
 }
 
@@ -15,9 +16,20 @@ impl TagBaseMapper for TagMapper {
     fn to_tag(&self) -> Result<String, ()> {
         let prefix = "Tag_".to_string();
         match self.key {
-            1 => Ok(format!("{}{}", prefix, "百名山".to_string())),
-            2 => Ok(format!("{}{}", prefix, "二百名山".to_string())),
+            1 => Ok(format!("{}{}", prefix, "百名山")),
+            2 => Ok(format!("{}{}", prefix, "二百名山")),
             _ => Err(()),
         }
     }
 }
+
+impl TagMapper {
+    /// タグ名から英語のタグ名を取得する
+    pub fn find_english_name(name: &str) -> Option<&'static str> {
+        match name {
+            "百名山" => Some("100 Famous Japanese Mountains"),
+            "二百名山" => Some("200 Famous Japanese Mountains"),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use crate::models::{get_value, Geometry, Location, ValueType, EARTH_RADIUS_KM};
+use aws_sdk_dynamodb::model::AttributeValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// ルートの軌跡を格納するテーブル
+///
+/// 軌跡は大きいため Mountains テーブルの Route_ 項目には含めず、ルートIDを Id（数値）とする
+/// 項目の Track 属性に格納する。/routes/{id}/track のみが読み込む。
+pub const ROUTE_TRACK_TABLE: &str = "RouteTracks";
+
+/// ルートの軌跡を構成する座標
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackPoint {
+    pub(crate) latitude: f64,
+    pub(crate) longitude: f64,
+    pub(crate) elevation: Option<f64>,
+}
+
+/// 軌跡から算出した統計値
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackStatistics {
+    /// 総距離（km）
+    pub distance: f64,
+    /// 累積登り標高（m）
+    pub ascent: f64,
+    /// 累積下り標高（m）
+    pub descent: f64,
+    pub max_elevation: Option<f64>,
+    pub min_elevation: Option<f64>,
+}
+
+impl TrackPoint {
+    fn to_location(&self) -> Location {
+        Location {
+            latitude: self.latitude,
+            longitude: self.longitude,
+            gsi_url: "".to_string(),
+        }
+    }
+
+    fn to_coordinates(&self) -> Vec<f64> {
+        match self.elevation {
+            Some(elevation) => vec![self.longitude, self.latitude, elevation],
+            None => vec![self.longitude, self.latitude],
+        }
+    }
+}
+
+/// DynamoDB の List(Map{Latitude, Longitude, Elevation}) を軌跡に変換する
+pub(crate) fn get_track(item: &HashMap<String, AttributeValue>, key: &str) -> Vec<TrackPoint> {
+    let mut track: Vec<TrackPoint> = Vec::new();
+
+    if let Some(value_attr) = item.get(key) {
+        if let Ok(points) = value_attr.as_l() {
+            for point in points {
+                if let Ok(point_value) = point.as_m() {
+                    let lat_key = String::from("Latitude");
+                    let lon_key = String::from("Longitude");
+                    let ele_key = String::from("Elevation");
+
+                    let latitude = get_value(point_value, &lat_key, ValueType::Number).parse();
+                    let longitude = get_value(point_value, &lon_key, ValueType::Number).parse();
+                    if let (Ok(latitude), Ok(longitude)) = (latitude, longitude) {
+                        track.push(TrackPoint {
+                            latitude,
+                            longitude,
+                            elevation: get_value(point_value, &ele_key, ValueType::Number)
+                                .parse()
+                                .ok(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    track
+}
+
+pub fn track_statistics(track: &[TrackPoint]) -> TrackStatistics {
+    let mut statistics = TrackStatistics {
+        distance: 0.0,
+        ascent: 0.0,
+        descent: 0.0,
+        max_elevation: None,
+        min_elevation: None,
+    };
+
+    for pair in track.windows(2) {
+        statistics.distance += pair[0].to_location().distance_to(&pair[1].to_location());
+    }
+
+    // 標高のない座標は飛ばし、直前の標高と比較する
+    let mut previous_elevation: Option<f64> = None;
+    for elevation in track.iter().filter_map(|point| point.elevation) {
+        if let Some(from) = previous_elevation {
+            if elevation > from {
+                statistics.ascent += elevation - from;
+            } else {
+                statistics.descent += from - elevation;
+            }
+        }
+        previous_elevation = Some(elevation);
+    }
+
+    for elevation in track.iter().filter_map(|point| point.elevation) {
+        statistics.max_elevation = Some(
+            statistics
+                .max_elevation
+                .map_or(elevation, |e| e.max(elevation)),
+        );
+        statistics.min_elevation = Some(
+            statistics
+                .min_elevation
+                .map_or(elevation, |e| e.min(elevation)),
+        );
+    }
+
+    statistics
+}
+
+/// 軌跡を LineString に変換する（RFC 7946 に従い、2点未満の場合は None とする）
+pub fn to_line_string(track: &[TrackPoint]) -> Option<Geometry> {
+    if track.len() < 2 {
+        return None;
+    }
+
+    Some(Geometry::LineString {
+        coordinates: track.iter().map(|point| point.to_coordinates()).collect(),
+    })
+}
+
+/// Douglas-Peucker 法で軌跡を間引く（tolerance は m）
+pub fn simplify_track(track: &[TrackPoint], tolerance: f64) -> Vec<TrackPoint> {
+    if track.len() < 3 {
+        return track.to_vec();
+    }
+
+    // 始点の緯度を基準に平面（m）へ投影する
+    let radius = EARTH_RADIUS_KM * 1000.0;
+    let cos_lat = track[0].latitude.to_radians().cos();
+    let projected: Vec<(f64, f64)> = track
+        .iter()
+        .map(|point| {
+            (
+                point.longitude.to_radians() * cos_lat * radius,
+                point.latitude.to_radians() * radius,
+            )
+        })
+        .collect();
+
+    let mut keep = vec![false; track.len()];
+    keep[0] = true;
+    keep[track.len() - 1] = true;
+
+    let mut stack = vec![(0, track.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max_distance = 0.0;
+        let mut max_index = start;
+        for (i, point) in projected.iter().enumerate().take(end).skip(start + 1) {
+            let distance = segment_distance(*point, projected[start], projected[end]);
+            if distance > max_distance {
+                max_distance = distance;
+                max_index = i;
+            }
+        }
+
+        if max_distance > tolerance {
+            keep[max_index] = true;
+            stack.push((start, max_index));
+            stack.push((max_index, end));
+        }
+    }
+
+    track
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(point, _)| point.clone())
+        .collect()
+}
+
+/// 点から線分までの距離
+fn segment_distance(point: (f64, f64), start: (f64, f64), end: (f64, f64)) -> f64 {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = dx * dx + dy * dy;
+    if length == 0.0 {
+        return ((point.0 - start.0).powi(2) + (point.1 - start.1).powi(2)).sqrt();
+    }
+
+    let t = (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length).clamp(0.0, 1.0);
+    let (x, y) = (start.0 + t * dx, start.1 + t * dy);
+
+    ((point.0 - x).powi(2) + (point.1 - y).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(latitude: f64, longitude: f64, elevation: Option<f64>) -> TrackPoint {
+        TrackPoint {
+            latitude,
+            longitude,
+            elevation,
+        }
+    }
+
+    #[test]
+    fn statistics_with_missing_elevations() {
+        let track = vec![
+            point(35.000, 138.0, Some(1000.0)),
+            point(35.001, 138.0, Some(1100.0)),
+            point(35.002, 138.0, None),
+            point(35.003, 138.0, Some(1050.0)),
+            point(35.004, 138.0, Some(1000.0)),
+            point(35.005, 138.0, None),
+        ];
+        let statistics = track_statistics(&track);
+
+        // 緯度0.001度は約111.19m
+        assert!((statistics.distance - 0.556).abs() < 0.001);
+        assert_eq!(statistics.ascent, 100.0);
+        assert_eq!(statistics.descent, 100.0);
+        assert_eq!(statistics.max_elevation, Some(1100.0));
+        assert_eq!(statistics.min_elevation, Some(1000.0));
+    }
+
+    #[test]
+    fn statistics_without_elevations() {
+        let track = vec![point(35.0, 138.0, None), point(35.001, 138.0, None)];
+        let statistics = track_statistics(&track);
+
+        assert_eq!(statistics.ascent, 0.0);
+        assert_eq!(statistics.descent, 0.0);
+        assert_eq!(statistics.max_elevation, None);
+        assert_eq!(statistics.min_elevation, None);
+
+        assert_eq!(track_statistics(&[]).distance, 0.0);
+    }
+
+    #[test]
+    fn simplify_collinear_track_to_endpoints() {
+        let track: Vec<TrackPoint> = (0..10)
+            .map(|i| point(35.0 + i as f64 * 0.001, 138.0, Some(1000.0 + i as f64)))
+            .collect();
+        let simplified = simplify_track(&track, 1.0);
+
+        assert_eq!(simplified, vec![track[0].clone(), track[9].clone()]);
+    }
+
+    #[test]
+    fn simplify_keeps_points_beyond_tolerance() {
+        // 中間点は始点と終点を結ぶ線分から約111m離れている
+        let track = vec![
+            point(35.000, 138.000, None),
+            point(35.001, 138.001, None),
+            point(35.000, 138.002, None),
+        ];
+
+        assert_eq!(simplify_track(&track, 10.0), track);
+        assert_eq!(
+            simplify_track(&track, 200.0),
+            vec![track[0].clone(), track[2].clone()]
+        );
+
+        // 3点未満はそのまま返す
+        assert_eq!(simplify_track(&track[..2], 1000.0), track[..2].to_vec());
+    }
+
+    #[test]
+    fn segment_distance_is_clamped_to_endpoints() {
+        assert_eq!(segment_distance((1.0, 1.0), (0.0, 0.0), (2.0, 0.0)), 1.0);
+        assert_eq!(segment_distance((5.0, 4.0), (0.0, 0.0), (2.0, 0.0)), 5.0);
+        assert_eq!(segment_distance((-3.0, 4.0), (0.0, 0.0), (2.0, 0.0)), 5.0);
+        assert_eq!(segment_distance((3.0, 4.0), (0.0, 0.0), (0.0, 0.0)), 5.0);
+    }
+
+    #[test]
+    fn line_string_requires_two_points() {
+        assert_eq!(to_line_string(&[]), None);
+        assert_eq!(to_line_string(&[point(35.0, 138.0, None)]), None);
+        assert_eq!(
+            to_line_string(&[point(35.0, 138.0, Some(1000.0)), point(35.1, 138.1, None)]),
+            Some(Geometry::LineString {
+                coordinates: vec![vec![138.0, 35.0, 1000.0], vec![138.1, 35.1]],
+            })
+        );
+    }
+}
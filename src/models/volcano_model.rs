@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 噴火警戒レベルの最大値
+pub const MAX_ALERT_LEVEL: u8 = 5;
+
+/// 火山情報
+#[derive(Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Volcano {
+    /// 活火山
+    pub(crate) active: bool,
+    /// 気象庁の常時観測火山
+    pub(crate) monitored: bool,
+    /// 噴火警戒レベル（1〜5、未導入の火山は None）
+    pub(crate) alert_level: Option<u8>,
+}
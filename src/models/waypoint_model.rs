@@ -0,0 +1,228 @@
+use crate::i18n::{Language, Message};
+use crate::models::Mountain;
+
+/// 山岳一覧を GPX 1.1 のウェイポイントに変換する
+pub fn to_mountains_gpx(mountains: &[Mountain], language: &Language) -> String {
+    let mut gpx = String::new();
+    gpx.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    gpx.push('\n');
+    gpx.push_str(concat!(
+        r#"<gpx version="1.1" creator="mountix" "#,
+        r#"xmlns="http://www.topografix.com/GPX/1/1" "#,
+        r#"xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" "#,
+        r#"xsi:schemaLocation="http://www.topografix.com/GPX/1/1 "#,
+        r#"http://www.topografix.com/GPX/1/1/gpx.xsd">"#
+    ));
+    gpx.push('\n');
+
+    // wptType の子要素は ele, name, desc の順とする
+    for mountain in mountains {
+        gpx.push_str(&format!(
+            "  <wpt lat=\"{}\" lon=\"{}\">\n",
+            mountain.location.latitude, mountain.location.longitude
+        ));
+        gpx.push_str(&format!("    <ele>{}</ele>\n", mountain.elevation));
+        gpx.push_str(&format!(
+            "    <name>{}</name>\n",
+            escape_xml(&mountain.name)
+        ));
+        gpx.push_str(&format!(
+            "    <desc>{}</desc>\n",
+            escape_xml(&waypoint_description(mountain, language))
+        ));
+        gpx.push_str("  </wpt>\n");
+    }
+
+    gpx.push_str("</gpx>\n");
+    gpx
+}
+
+/// 山岳一覧を KML 2.2 の Placemark に変換する
+pub fn to_mountains_kml(mountains: &[Mountain], language: &Language) -> String {
+    let mut kml = String::new();
+    kml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    kml.push('\n');
+    kml.push_str(r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#);
+    kml.push('\n');
+    kml.push_str("  <Document>\n");
+    kml.push_str("    <name>mountix</name>\n");
+
+    for mountain in mountains {
+        kml.push_str(&format!(
+            "    <Placemark id=\"mountain-{}\">\n",
+            mountain.id
+        ));
+        kml.push_str(&format!(
+            "      <name>{}</name>\n",
+            escape_xml(&mountain.name)
+        ));
+        kml.push_str(&format!(
+            "      <description>{}</description>\n",
+            escape_xml(&waypoint_description(mountain, language))
+        ));
+        kml.push_str("      <Point>\n");
+        kml.push_str("        <altitudeMode>absolute</altitudeMode>\n");
+        kml.push_str(&format!(
+            "        <coordinates>{},{},{}</coordinates>\n",
+            mountain.location.longitude, mountain.location.latitude, mountain.elevation
+        ));
+        kml.push_str("      </Point>\n");
+        kml.push_str("    </Placemark>\n");
+    }
+
+    kml.push_str("  </Document>\n");
+    kml.push_str("</kml>\n");
+    kml
+}
+
+/// ウェイポイントの説明（よみ、都道府県、タグ）
+fn waypoint_description(mountain: &Mountain, language: &Language) -> String {
+    let separator = Message::WaypointSeparator.text(language);
+    let mut lines: Vec<String> = vec![format!(
+        "{}: {}",
+        Message::WaypointKana.text(language),
+        mountain.name_kana
+    )];
+    if !mountain.prefectures.is_empty() {
+        lines.push(format!(
+            "{}: {}",
+            Message::WaypointPrefectures.text(language),
+            mountain.prefectures.join(&separator)
+        ));
+    }
+    if !mountain.tags.is_empty() {
+        lines.push(format!(
+            "{}: {}",
+            Message::WaypointTags.text(language),
+            mountain.tags.join(&separator)
+        ));
+    }
+    lines.join("\n")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Location;
+
+    fn mountain() -> Mountain {
+        Mountain {
+            id: 1,
+            name: "\"槍\" & <穂高>'s".to_string(),
+            name_kana: "やりほたか".to_string(),
+            prefectures: vec!["長野県".to_string(), "岐阜県".to_string()],
+            elevation: 3180.0,
+            location: Location {
+                latitude: 36.341944,
+                longitude: 137.6475,
+                gsi_url: "".to_string(),
+            },
+            tags: vec!["百名山".to_string()],
+            ..Default::default()
+        }
+    }
+
+    /// 子要素が指定した順に出現することを確認する
+    fn assert_in_order(xml: &str, elements: &[&str]) {
+        let mut position = 0;
+        for element in elements {
+            match xml[position..].find(element) {
+                Some(index) => position += index + element.len(),
+                None => panic!("{} が順に出現しません: {}", element, xml),
+            }
+        }
+    }
+
+    #[test]
+    fn gpx_uses_gpx11_namespace_and_wpt_order() {
+        let gpx = to_mountains_gpx(&[mountain()], &Language::Ja);
+
+        assert!(gpx.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gpx "));
+        assert!(gpx.contains(r#"version="1.1""#));
+        assert!(gpx.contains(r#"xmlns="http://www.topografix.com/GPX/1/1""#));
+        assert!(gpx.contains(
+            r#"xsi:schemaLocation="http://www.topografix.com/GPX/1/1 http://www.topografix.com/GPX/1/1/gpx.xsd""#
+        ));
+        // wptType は ele, name, desc の順
+        assert_in_order(
+            &gpx,
+            &[
+                r#"<wpt lat="36.341944" lon="137.6475">"#,
+                "<ele>3180</ele>",
+                "<name>",
+                "<desc>",
+                "</wpt>",
+                "</gpx>",
+            ],
+        );
+    }
+
+    #[test]
+    fn kml_uses_kml22_namespace_and_placemark_order() {
+        let kml = to_mountains_kml(&[mountain()], &Language::Ja);
+
+        assert!(kml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<kml "));
+        assert!(kml.contains(r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#));
+        // Feature の name, description の後に Geometry、Point は altitudeMode, coordinates の順
+        assert_in_order(
+            &kml,
+            &[
+                "<Document>",
+                r#"<Placemark id="mountain-1">"#,
+                "<name>",
+                "<description>",
+                "<Point>",
+                "<altitudeMode>absolute</altitudeMode>",
+                "<coordinates>137.6475,36.341944,3180</coordinates>",
+                "</Point>",
+                "</Placemark>",
+                "</Document>",
+                "</kml>",
+            ],
+        );
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let escaped = "<name>&quot;槍&quot; &amp; &lt;穂高&gt;&apos;s</name>";
+        let gpx = to_mountains_gpx(&[mountain()], &Language::Ja);
+        let kml = to_mountains_kml(&[mountain()], &Language::Ja);
+
+        assert!(gpx.contains(escaped));
+        assert!(kml.contains(escaped));
+        assert!(!gpx.contains("& <"));
+        assert_eq!(escape_xml("a&amp;b"), "a&amp;amp;b");
+    }
+
+    #[test]
+    fn description_is_localized() {
+        let gpx = to_mountains_gpx(&[mountain()], &Language::Ja);
+        assert!(
+            gpx.contains("<desc>よみ: やりほたか\n都道府県: 長野県、岐阜県\nタグ: 百名山</desc>")
+        );
+
+        let kml = to_mountains_kml(&[mountain()], &Language::En);
+        assert!(kml.contains(
+            "<description>Kana: やりほたか\nPrefectures: 長野県, 岐阜県\nTags: 百名山</description>"
+        ));
+    }
+
+    #[test]
+    fn empty_list_is_still_a_document() {
+        let gpx = to_mountains_gpx(&[], &Language::Ja);
+        assert!(gpx.ends_with("</gpx>\n"));
+        assert!(!gpx.contains("<wpt"));
+
+        let kml = to_mountains_kml(&[], &Language::Ja);
+        assert_in_order(&kml, &["<Document>", "<name>mountix</name>", "</Document>"]);
+    }
+}
@@ -0,0 +1,3 @@
+mod openapi_document;
+
+pub use openapi_document::*;
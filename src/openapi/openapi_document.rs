@@ -0,0 +1,584 @@
+use crate::endpoints::{
+    ApiInfoResponse, HutListResponse, MountainRangeListResponse, NearbyHutListResponse,
+    ParkListResponse, ResponseType, RouteListResponse, SubPeakListResponse,
+};
+use crate::models::{
+    Feature, Hut, Mountain, MountainListResponse, Problem, Route, MAX_ALERT_LEVEL,
+};
+use crate::queries::{COMMON_PARAMETERS, MOUNTAIN_QUERY_PARAMETERS, SORT_KEYS};
+use crate::serializers::BodyFormat;
+use crate::services::{ComparedMountainResult, NearbyMountainResult, TrackProperties};
+use schemars::gen::SchemaSettings;
+use schemars::schema::Schema;
+use serde_json::{json, Map, Value};
+
+pub const OPENAPI_VERSION: &str = "3.0.2";
+
+/// Rust の型から OpenAPI 3 のドキュメントを生成する
+///
+/// paths は crate::endpoints::ENDPOINTS の全てのパスを記述する。
+pub fn openapi_document() -> Value {
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let api_info = schema_value(&generator.subschema_for::<ApiInfoResponse>());
+    let mountain = schema_value(&generator.subschema_for::<Mountain>());
+    let mountain_list = schema_value(&generator.subschema_for::<MountainListResponse>());
+    let compared = schema_value(&generator.subschema_for::<ComparedMountainResult>());
+    let sub_peak_list = schema_value(&generator.subschema_for::<SubPeakListResponse>());
+    let nearby_list = schema_value(&generator.subschema_for::<NearbyMountainResult>());
+    let range_list = schema_value(&generator.subschema_for::<MountainRangeListResponse>());
+    let park_list = schema_value(&generator.subschema_for::<ParkListResponse>());
+    let route = schema_value(&generator.subschema_for::<Route>());
+    let route_list = schema_value(&generator.subschema_for::<RouteListResponse>());
+    let track = schema_value(&generator.subschema_for::<Feature<TrackProperties>>());
+    let hut = schema_value(&generator.subschema_for::<Hut>());
+    let hut_list = schema_value(&generator.subschema_for::<HutListResponse>());
+    let nearby_hut_list = schema_value(&generator.subschema_for::<NearbyHutListResponse>());
+    let problem = schema_value(&generator.subschema_for::<Problem>());
+
+    let mut schemas = Map::new();
+    for (name, schema) in generator.take_definitions() {
+        schemas.insert(name, schema_value(&schema));
+    }
+
+    let list_parameters: Vec<Value> = MOUNTAIN_QUERY_PARAMETERS
+        .iter()
+        .chain(COMMON_PARAMETERS.iter())
+        .map(|name| common_parameter(name, ResponseType::MountainList))
+        .collect();
+    let range_list_parameters: Vec<Value> = [id_parameter("山脈ID")]
+        .into_iter()
+        .chain(
+            MOUNTAIN_QUERY_PARAMETERS
+                .iter()
+                .chain(COMMON_PARAMETERS.iter())
+                .filter(|name| **name != "range")
+                .map(|name| common_parameter(name, ResponseType::MountainRangeMountainList)),
+        )
+        .collect();
+    let detail_parameters: Vec<Value> = [id_parameter("山岳ID"), query_parameter("rank")]
+        .into_iter()
+        .chain(response_parameters(ResponseType::Mountain))
+        .collect();
+
+    let operation = |operation_id: &str,
+                     summary: &str,
+                     parameters: Vec<Value>,
+                     response_type: ResponseType,
+                     description: &str,
+                     schema: &Value,
+                     problem_statuses: &[u16]| {
+        let mut responses = Map::new();
+        responses.insert(
+            "200".to_string(),
+            json!({
+                "description": description,
+                "content": content(response_type, schema)
+            }),
+        );
+        for status in problem_statuses {
+            responses.insert(
+                status.to_string(),
+                json!({
+                    "description": problem_description(*status),
+                    "content": {
+                        "application/problem+json": { "schema": problem }
+                    }
+                }),
+            );
+        }
+        json!({
+            "get": {
+                "operationId": operation_id,
+                "summary": summary,
+                "parameters": parameters,
+                "responses": responses
+            }
+        })
+    };
+    let with_response_parameters = |parameters: Vec<Value>, response_type: ResponseType| {
+        parameters
+            .into_iter()
+            .chain(response_parameters(response_type))
+            .collect::<Vec<Value>>()
+    };
+
+    let radius = |default: f64| {
+        parameter(
+            "radius",
+            json!({ "type": "number", "minimum": 0, "exclusiveMinimum": true, "default": default }),
+            "検索半径（km）",
+        )
+    };
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "mountix API",
+            "description": "日本の主な山岳を提供するAPIです。",
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "servers": [{ "url": "https://mountix.codemountains.org/api/v1" }],
+        "paths": {
+            "/": operation(
+                "getApiInfo",
+                "API の情報を取得する",
+                response_parameters(ResponseType::ApiInfo),
+                ResponseType::ApiInfo,
+                "API の情報",
+                &api_info,
+                &[400, 406, 500],
+            ),
+            "/openapi.json": operation(
+                "getOpenApiDocument",
+                "OpenAPI ドキュメントを取得する",
+                response_parameters(ResponseType::OpenApi),
+                ResponseType::OpenApi,
+                "OpenAPI ドキュメント",
+                &json!({ "type": "object" }),
+                &[400, 406],
+            ),
+            "/mountains": operation(
+                "listMountains",
+                "山岳一覧を検索する",
+                list_parameters,
+                ResponseType::MountainList,
+                "山岳一覧",
+                &mountain_list,
+                &[400, 406, 500],
+            ),
+            "/mountains/compare": operation(
+                "compareMountains",
+                "山岳を比較する",
+                with_response_parameters(
+                    vec![required(parameter(
+                        "ids",
+                        json!({ "type": "string", "pattern": "^[0-9]+(,[0-9]+){1,2}$" }),
+                        "比較する山岳ID（カンマ区切りで2〜3件、重複不可）",
+                    ))],
+                    ResponseType::CompareMountains,
+                ),
+                ResponseType::CompareMountains,
+                "山岳と、山岳同士の差分",
+                &compared,
+                &[400, 404, 406],
+            ),
+            "/mountains/{id}": operation(
+                "getMountain",
+                "山岳情報を取得する",
+                detail_parameters,
+                ResponseType::Mountain,
+                "山岳情報",
+                &mountain,
+                &[400, 404, 406],
+            ),
+            "/mountains/{id}/peaks": operation(
+                "listSubPeaks",
+                "子の山岳一覧を取得する",
+                with_response_parameters(vec![id_parameter("山岳ID")], ResponseType::SubPeakList),
+                ResponseType::SubPeakList,
+                "子の山岳一覧",
+                &sub_peak_list,
+                &[400, 404, 406],
+            ),
+            "/mountains/{id}/routes": operation(
+                "listMountainRoutes",
+                "山岳の登山ルート一覧を取得する",
+                with_response_parameters(vec![id_parameter("山岳ID")], ResponseType::RouteList),
+                ResponseType::RouteList,
+                "登山ルート一覧",
+                &route_list,
+                &[400, 404, 406],
+            ),
+            "/mountains/{id}/huts": operation(
+                "listMountainHuts",
+                "山岳に紐づく山小屋一覧を取得する",
+                with_response_parameters(
+                    vec![id_parameter("山岳ID")],
+                    ResponseType::MountainHutList,
+                ),
+                ResponseType::MountainHutList,
+                "山小屋一覧",
+                &hut_list,
+                &[400, 404, 406],
+            ),
+            "/mountains/{id}/nearby": operation(
+                "listNearbyMountains",
+                "周辺の山岳一覧を取得する",
+                with_response_parameters(
+                    vec![
+                        id_parameter("山岳ID"),
+                        radius(20.0),
+                        parameter(
+                            "limit",
+                            json!({ "type": "integer", "minimum": 1, "default": 10 }),
+                            "取得件数",
+                        ),
+                    ],
+                    ResponseType::NearbyMountainList,
+                ),
+                ResponseType::NearbyMountainList,
+                "周辺の山岳一覧（近い順）",
+                &nearby_list,
+                &[400, 404, 406],
+            ),
+            "/ranges": operation(
+                "listMountainRanges",
+                "山脈・山地の一覧を取得する",
+                response_parameters(ResponseType::MountainRangeList),
+                ResponseType::MountainRangeList,
+                "山脈・山地の一覧",
+                &range_list,
+                &[400, 406, 500],
+            ),
+            "/ranges/{id}/mountains": operation(
+                "listMountainRangeMountains",
+                "山脈・山地に属する山岳一覧を検索する",
+                range_list_parameters,
+                ResponseType::MountainRangeMountainList,
+                "山脈・山地に属する山岳一覧",
+                &mountain_list,
+                &[400, 404, 406, 500],
+            ),
+            "/parks": operation(
+                "listParks",
+                "国立公園の一覧を取得する",
+                response_parameters(ResponseType::ParkList),
+                ResponseType::ParkList,
+                "国立公園と、区域内にある山岳の数",
+                &park_list,
+                &[400, 406, 500],
+            ),
+            "/routes/{id}": operation(
+                "getRoute",
+                "登山ルートを取得する",
+                with_response_parameters(vec![id_parameter("ルートID")], ResponseType::Route),
+                ResponseType::Route,
+                "登山ルート",
+                &route,
+                &[400, 404, 406],
+            ),
+            "/routes/{id}/track": operation(
+                "getRouteTrack",
+                "登山ルートの軌跡を取得する",
+                with_response_parameters(
+                    vec![
+                        id_parameter("ルートID"),
+                        parameter(
+                            "simplify",
+                            json!({ "type": "number", "minimum": 0, "exclusiveMinimum": true }),
+                            "Douglas-Peucker 法で間引く許容誤差（m）",
+                        ),
+                    ],
+                    ResponseType::RouteTrack,
+                ),
+                ResponseType::RouteTrack,
+                "軌跡の LineString と統計値（GeoJSON の Feature）",
+                &track,
+                &[400, 404, 406],
+            ),
+            "/huts": operation(
+                "listHuts",
+                "山小屋の一覧を取得する",
+                with_response_parameters(
+                    vec![
+                        parameter(
+                            "latitude",
+                            json!({ "type": "number", "minimum": -90, "maximum": 90 }),
+                            "緯度（longitude と合わせて指定すると周辺の山小屋を近い順に返す）",
+                        ),
+                        parameter(
+                            "longitude",
+                            json!({ "type": "number", "minimum": -180, "maximum": 180 }),
+                            "経度",
+                        ),
+                        radius(10.0),
+                    ],
+                    ResponseType::HutList,
+                ),
+                ResponseType::HutList,
+                "山小屋一覧（latitude、longitude を指定した場合は距離を含む）",
+                &json!({ "oneOf": [hut_list, nearby_hut_list] }),
+                &[400, 406, 500],
+            ),
+            "/huts/{id}": operation(
+                "getHut",
+                "山小屋を取得する",
+                with_response_parameters(vec![id_parameter("山小屋ID")], ResponseType::Hut),
+                ResponseType::Hut,
+                "山小屋",
+                &hut,
+                &[400, 404, 406],
+            )
+        },
+        "components": { "schemas": schemas }
+    })
+}
+
+/// クエリパラメータのスキーマと説明
+///
+/// 未定義のパラメータは None を返す（ドキュメントとの乖離をテストで検出する）。
+pub fn query_parameter_schema(name: &str) -> Option<(Value, &'static str)> {
+    let boolean = json!({ "type": "boolean" });
+    let id = json!({ "type": "integer", "format": "uint32", "minimum": 1 });
+
+    let schema = match name {
+        "prefecture" => (id, "都道府県ID（JIS X 0401）"),
+        "tag" => (id, "タグID（1: 百名山、2: 二百名山）"),
+        "range" => (id, "山脈ID"),
+        "park" => (id, "国立公園ID"),
+        "name" => (json!({ "type": "string" }), "山名（別名を含む部分一致）"),
+        "top_level_only" => (boolean, "主峰のみに絞り込む"),
+        "primary_prefecture" => (id, "主たる都道府県ID（JIS X 0401）"),
+        "volcano" => (boolean, "火山かどうかで絞り込む"),
+        "alert_level_min" => (
+            json!({ "type": "integer", "minimum": 1, "maximum": MAX_ALERT_LEVEL }),
+            "噴火警戒レベル（指定したレベル以上）",
+        ),
+        "in_season" => (
+            json!({ "type": "integer", "minimum": 1, "maximum": 12 }),
+            "登山適期の月",
+        ),
+        "difficulty_max" => (
+            json!({ "type": "string", "pattern": "^[A-Ea-e]([1-9]|10)?$" }),
+            "難易度の上限（技術的難易度、または技術的難易度と体力度。例: C、C5）。いずれかのルートが上限以下の山岳を返す",
+        ),
+        "offset" => (
+            json!({ "type": "integer", "minimum": 0, "default": 0 }),
+            "取得開始位置（CSV では無視し、全件を出力する）",
+        ),
+        "limit" => (
+            json!({ "type": "integer", "minimum": 1 }),
+            "取得件数（CSV では無視し、全件を出力する）",
+        ),
+        "sort" => (
+            json!({ "type": "string", "enum": SORT_KEYS }),
+            "並び順（省略時は ID の昇順）",
+        ),
+        "format" => (
+            json!({
+                "type": "string",
+                "enum": ["json", "geojson", "csv", "gpx", "kml", "msgpack", "cbor"]
+            }),
+            "レスポンスの形式（Accept ヘッダーより優先する）",
+        ),
+        "pretty" => (
+            json!({ "type": "boolean", "default": true }),
+            "JSON を整形して返す",
+        ),
+        "lang" => (
+            json!({ "type": "string", "enum": ["ja", "en"] }),
+            "レスポンスの言語（Accept-Language ヘッダーより優先する）",
+        ),
+        "bom" => (boolean, "CSV に BOM を付与する"),
+        "strict" => (boolean, "未対応のパラメータをエラーとする"),
+        "rank" => (boolean, "標高順位を含める"),
+        _ => return None,
+    };
+
+    Some(schema)
+}
+
+fn query_parameter(name: &str) -> Value {
+    let (schema, description) =
+        query_parameter_schema(name).unwrap_or((json!({ "type": "string" }), ""));
+    parameter(name, schema, description)
+}
+
+fn parameter(name: &str, schema: Value, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": schema
+    })
+}
+
+fn required(mut parameter: Value) -> Value {
+    parameter["required"] = Value::Bool(true);
+    parameter
+}
+
+/// format の候補をエンドポイントで選択できる形式に限定する
+fn common_parameter(name: &str, response_type: ResponseType) -> Value {
+    if name != "format" {
+        return query_parameter(name);
+    }
+
+    let mut format = query_parameter(name);
+    let formats: Vec<&str> = response_type
+        .allowed_formats()
+        .iter()
+        .map(|format| format.format_param())
+        .collect();
+    format["schema"]["enum"] = json!(formats);
+    format
+}
+
+/// 全てのエンドポイントで受け付けるパラメータ（format、pretty、lang）
+fn response_parameters(response_type: ResponseType) -> Vec<Value> {
+    ["format", "pretty", "lang"]
+        .iter()
+        .map(|name| common_parameter(name, response_type))
+        .collect()
+}
+
+fn id_parameter(description: &str) -> Value {
+    json!({
+        "name": "id",
+        "in": "path",
+        "required": true,
+        "description": description,
+        "schema": { "type": "integer", "format": "uint32" }
+    })
+}
+
+/// エンドポイントで選択できる形式ごとのレスポンス
+///
+/// JSON、MessagePack、CBOR は同じスキーマで、CSV、GPX、KML は文字列として記述する。
+/// 山岳の GeoJSON は Feature、FeatureCollection に変換するため object とする。
+fn content(response_type: ResponseType, schema: &Value) -> Value {
+    let mut content = Map::new();
+    for format in response_type.allowed_formats() {
+        let media_type = format.content_type().split(';').next().unwrap_or("");
+        let format_schema = match format {
+            BodyFormat::Json { .. } | BodyFormat::MessagePack | BodyFormat::Cbor => schema.clone(),
+            BodyFormat::GeoJson if response_type == ResponseType::RouteTrack => schema.clone(),
+            BodyFormat::GeoJson => json!({ "type": "object" }),
+            BodyFormat::Csv | BodyFormat::Gpx | BodyFormat::Kml => json!({ "type": "string" }),
+        };
+        content.insert(media_type.to_string(), json!({ "schema": format_schema }));
+    }
+    Value::Object(content)
+}
+
+fn problem_description(status: u16) -> &'static str {
+    match status {
+        400 => "不正なパラメータ",
+        404 => "リソースが見つからない",
+        406 => "対応していない形式",
+        _ => "サーバーエラー",
+    }
+}
+
+fn schema_value(schema: &Schema) -> Value {
+    serde_json::to_value(schema).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::{response_type, ENDPOINTS};
+    use crate::queries::MountainQuery;
+    use std::collections::BTreeSet;
+
+    const SNAPSHOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    /// 型を変更した場合は `UPDATE_OPENAPI=1 cargo test` で openapi.json を更新する
+    #[test]
+    fn openapi_document_matches_snapshot() {
+        let document = openapi_document();
+        if std::env::var("UPDATE_OPENAPI").is_ok() {
+            let json = serde_json::to_string_pretty(&document).unwrap();
+            std::fs::write(SNAPSHOT_PATH, format!("{}\n", json)).unwrap();
+        }
+
+        let snapshot: Value =
+            serde_json::from_str(&std::fs::read_to_string(SNAPSHOT_PATH).unwrap()).unwrap();
+        assert_eq!(
+            document, snapshot,
+            "openapi.json と型が一致しません。UPDATE_OPENAPI=1 cargo test で更新してください。"
+        );
+    }
+
+    #[test]
+    fn every_query_parameter_is_documented() {
+        for name in MOUNTAIN_QUERY_PARAMETERS
+            .iter()
+            .chain(COMMON_PARAMETERS.iter())
+        {
+            assert!(
+                query_parameter_schema(name).is_some(),
+                "{} が OpenAPI ドキュメントに定義されていません。",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn mountain_schema_matches_serialized_fields() {
+        let document = openapi_document();
+        let properties = document["components"]["schemas"]["Mountain"]["properties"]
+            .as_object()
+            .unwrap();
+        for field in [
+            "id",
+            "name",
+            "nameKana",
+            "area",
+            "prefectures",
+            "elevation",
+            "location",
+            "tags",
+            "rank",
+        ] {
+            assert!(properties.contains_key(field), "{} がありません。", field);
+        }
+
+        let location = document["components"]["schemas"]["Location"]["properties"]
+            .as_object()
+            .unwrap();
+        let location_fields: Vec<&str> = location.keys().map(|key| key.as_str()).collect();
+        assert_eq!(location_fields, vec!["gsiUrl", "latitude", "longitude"]);
+    }
+
+    #[test]
+    fn sort_documentation_matches_query_default() {
+        let (schema, description) = query_parameter_schema("sort").unwrap();
+        let default_sort = MountainQuery::default().sort;
+
+        // 省略時の "id" は enum の値ではないため default は記述しない
+        assert!(!SORT_KEYS.contains(&default_sort.as_str()));
+        assert!(schema.get("default").is_none());
+        assert!(description.contains("ID の昇順"));
+    }
+
+    #[test]
+    fn every_endpoint_is_documented() {
+        let document = openapi_document();
+        let paths = document["paths"].as_object().unwrap();
+
+        let documented: BTreeSet<&str> = paths.keys().map(|path| path.as_str()).collect();
+        let endpoints: BTreeSet<&str> = ENDPOINTS.iter().map(|(path, _)| *path).collect();
+        assert_eq!(documented, endpoints);
+
+        for (path, expected) in ENDPOINTS {
+            let documented_type = response_type(&path.replace("{id}", "1"));
+            assert_eq!(documented_type, expected, "{}", path);
+            assert_ne!(documented_type, ResponseType::Error, "{}", path);
+
+            // 200 のメディアタイプはエンドポイントで選択できる形式と一致する
+            let content = paths[path]["get"]["responses"]["200"]["content"]
+                .as_object()
+                .unwrap();
+            let media_types: BTreeSet<&str> = content.keys().map(|key| key.as_str()).collect();
+            let allowed: BTreeSet<&str> = expected
+                .allowed_formats()
+                .iter()
+                .map(|format| format.content_type().split(';').next().unwrap())
+                .collect();
+            assert_eq!(media_types, allowed, "{}", path);
+        }
+    }
+
+    #[test]
+    fn every_template_event_is_an_endpoint() {
+        let template = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/template.yaml"));
+        let events: BTreeSet<&str> = template
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("Path: "))
+            .collect();
+        let endpoints: BTreeSet<&str> = ENDPOINTS.iter().map(|(path, _)| *path).collect();
+        assert_eq!(events, endpoints);
+    }
+}
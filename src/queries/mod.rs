@@ -0,0 +1,3 @@
+mod mountain_query;
+
+pub use mountain_query::*;
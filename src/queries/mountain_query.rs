@@ -0,0 +1,511 @@
+use crate::i18n::Message;
+use crate::models::{
+    DifficultyLimit, MountainRangeBaseMapper, MountainRangeMapper, ParameterError, ParkBaseMapper,
+    ParkMapper, PrefectureBaseMapper, PrefectureMapper, TagBaseMapper, TagMapper, MAX_ALERT_LEVEL,
+};
+use crate::services::{FilterCondition, RangeCondition, SearchCondition, SearchType};
+use lambda_http::StrMap;
+
+/// 山岳一覧の検索・絞り込み以外に受け付けるパラメータ
+pub const COMMON_PARAMETERS: [&str; 5] = ["format", "pretty", "lang", "bom", "strict"];
+
+/// 山岳一覧の検索・絞り込みのパラメータ
+pub const MOUNTAIN_QUERY_PARAMETERS: [&str; 14] = [
+    "prefecture",
+    "tag",
+    "range",
+    "park",
+    "name",
+    "top_level_only",
+    "primary_prefecture",
+    "volcano",
+    "alert_level_min",
+    "in_season",
+    "difficulty_max",
+    "offset",
+    "limit",
+    "sort",
+];
+
+pub const SORT_KEYS: [&str; 8] = [
+    "id.asc",
+    "id.desc",
+    "elevation.asc",
+    "elevation.desc",
+    "name.asc",
+    "name.desc",
+    "difficulty.asc",
+    "difficulty.desc",
+];
+
+/// 山岳一覧のクエリパラメータ
+#[derive(Debug, PartialEq, Clone)]
+pub struct MountainQuery {
+    pub prefecture: Option<u32>,
+    pub tag: Option<u32>,
+    pub range: Option<u32>,
+    pub park: Option<u32>,
+    pub name: Option<String>,
+    pub top_level_only: bool,
+    pub primary_prefecture: Option<u32>,
+    pub volcano: Option<bool>,
+    pub alert_level_min: Option<u8>,
+    pub in_season: Option<u32>,
+    pub difficulty_max: Option<DifficultyLimit>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+    pub sort: String,
+    /// strict=true の場合は未対応のパラメータをエラーとする
+    pub strict: bool,
+}
+
+impl Default for MountainQuery {
+    fn default() -> Self {
+        Self {
+            prefecture: None,
+            tag: None,
+            range: None,
+            park: None,
+            name: None,
+            top_level_only: false,
+            primary_prefecture: None,
+            volcano: None,
+            alert_level_min: None,
+            in_season: None,
+            difficulty_max: None,
+            offset: 0,
+            limit: None,
+            sort: "id".to_string(),
+            strict: false,
+        }
+    }
+}
+
+impl MountainQuery {
+    /// クエリパラメータを検証し、不正なパラメータはすべてエラーとして返す
+    pub fn parse(query_params: &StrMap) -> Result<Self, Vec<ParameterError>> {
+        let mut query = MountainQuery::default();
+        let mut errors: Vec<ParameterError> = Vec::new();
+
+        // 検索条件: 都道府県ID
+        if let Some(pref) = query_params.get("prefecture") {
+            match parse_prefecture(pref) {
+                Some(pref_key) => query.prefecture = Some(pref_key),
+                None => errors.push(ParameterError::new(
+                    "prefecture",
+                    Message::InvalidPrefectureId,
+                )),
+            }
+        }
+
+        // 検索条件: タグ（百名山）
+        if let Some(tag) = query_params.get("tag") {
+            match tag.parse::<u32>() {
+                Ok(tag_key) if TagMapper::new(tag_key).to_tag().is_ok() => {
+                    query.tag = Some(tag_key)
+                }
+                _ => errors.push(ParameterError::new("tag", Message::InvalidTagId)),
+            }
+        }
+
+        // 検索条件: 山脈・山地
+        if let Some(range) = query_params.get("range") {
+            match range.parse::<u32>() {
+                Ok(range_key)
+                    if MountainRangeMapper::new(range_key)
+                        .to_mountain_range()
+                        .is_ok() =>
+                {
+                    query.range = Some(range_key)
+                }
+                _ => errors.push(ParameterError::new(
+                    "range",
+                    Message::InvalidMountainRangeId,
+                )),
+            }
+        }
+
+        // 検索条件: 国立公園
+        if let Some(park) = query_params.get("park") {
+            match park.parse::<u32>() {
+                Ok(park_key) if ParkMapper::new(park_key).to_park().is_ok() => {
+                    query.park = Some(park_key)
+                }
+                _ => errors.push(ParameterError::new("park", Message::InvalidParkId)),
+            }
+        }
+
+        // 検索条件: 山名
+        if let Some(mountain_name) = query_params.get("name") {
+            query.name = Some(mountain_name.to_string());
+        }
+
+        // 絞り込み条件: 主峰のみ
+        if let Some(top_level_only) = query_params.get("top_level_only") {
+            match parse_bool(top_level_only) {
+                Some(value) => query.top_level_only = value,
+                None => errors.push(ParameterError::new(
+                    "top_level_only",
+                    Message::InvalidTopLevelOnly,
+                )),
+            }
+        }
+
+        // 絞り込み条件: 主たる都道府県
+        if let Some(pref) = query_params.get("primary_prefecture") {
+            match parse_prefecture(pref) {
+                Some(pref_key) => query.primary_prefecture = Some(pref_key),
+                None => errors.push(ParameterError::new(
+                    "primary_prefecture",
+                    Message::InvalidPrefectureId,
+                )),
+            }
+        }
+
+        // 絞り込み条件: 火山
+        if let Some(volcano) = query_params.get("volcano") {
+            match parse_bool(volcano) {
+                Some(value) => query.volcano = Some(value),
+                None => errors.push(ParameterError::new("volcano", Message::InvalidVolcano)),
+            }
+        }
+
+        // 絞り込み条件: 噴火警戒レベル（指定したレベル以上）
+        if let Some(alert_level) = query_params.get("alert_level_min") {
+            match alert_level.parse::<u8>() {
+                Ok(level) if (1..=MAX_ALERT_LEVEL).contains(&level) => {
+                    query.alert_level_min = Some(level)
+                }
+                _ => errors.push(ParameterError::new(
+                    "alert_level_min",
+                    Message::InvalidAlertLevelMin,
+                )),
+            }
+        }
+
+        // 絞り込み条件: 登山適期
+        if let Some(month) = query_params.get("in_season") {
+            match month.parse::<u32>() {
+                Ok(month_value) if (1..=12).contains(&month_value) => {
+                    query.in_season = Some(month_value)
+                }
+                _ => errors.push(ParameterError::new("in_season", Message::InvalidInSeason)),
+            }
+        }
+
+        // 絞り込み条件: 難易度の上限（C: 技術的難易度のみ、C5: 技術的難易度と体力度）
+        if let Some(difficulty) = query_params.get("difficulty_max") {
+            match DifficultyLimit::try_from(difficulty) {
+                Ok(grade) => query.difficulty_max = Some(grade),
+                Err(_) => errors.push(ParameterError::new(
+                    "difficulty_max",
+                    Message::InvalidDifficultyMax,
+                )),
+            }
+        }
+
+        // offset 値チェック
+        if let Some(offset) = query_params.get("offset") {
+            match offset.parse::<usize>() {
+                Ok(offset_value) => query.offset = offset_value,
+                Err(_) => errors.push(ParameterError::new("offset", Message::InvalidOffset)),
+            }
+        }
+
+        // limit 値チェック
+        if let Some(limit) = query_params.get("limit") {
+            match limit.parse::<usize>() {
+                Ok(limit_value) if limit_value > 0 => query.limit = Some(limit_value),
+                _ => errors.push(ParameterError::new("limit", Message::InvalidLimit)),
+            }
+        }
+
+        // sort
+        if let Some(sort) = query_params.get("sort") {
+            let sort_key = sort.to_lowercase();
+            if SORT_KEYS.contains(&sort_key.as_str()) {
+                query.sort = sort_key;
+            } else {
+                errors.push(ParameterError::new("sort", Message::InvalidSort));
+            }
+        }
+
+        // strict: 未対応のパラメータをエラーとする
+        if let Some(strict) = query_params.get("strict") {
+            match parse_bool(strict) {
+                Some(value) => query.strict = value,
+                None => errors.push(ParameterError::new("strict", Message::InvalidStrict)),
+            }
+        }
+        if query.strict {
+            let mut unknown_parameters: Vec<&str> = Vec::new();
+            for (key, _) in query_params.iter() {
+                if !MOUNTAIN_QUERY_PARAMETERS.contains(&key)
+                    && !COMMON_PARAMETERS.contains(&key)
+                    && !unknown_parameters.contains(&key)
+                {
+                    unknown_parameters.push(key);
+                }
+            }
+            unknown_parameters.sort_unstable();
+            for key in unknown_parameters {
+                errors.push(ParameterError::new(key, Message::UnknownParameter));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(query)
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn search_conditions(&self) -> Vec<SearchCondition> {
+        let mut search_conditions: Vec<SearchCondition> = Vec::new();
+
+        if let Some(pref) = self
+            .prefecture
+            .and_then(|key| PrefectureMapper::new(key).to_prefecture().ok())
+        {
+            search_conditions.push(SearchCondition {
+                search_type: SearchType::Prefecture,
+                value: pref,
+            });
+        }
+        if let Some(tag) = self.tag.and_then(|key| TagMapper::new(key).to_tag().ok()) {
+            search_conditions.push(SearchCondition {
+                search_type: SearchType::Tag,
+                value: tag,
+            });
+        }
+        if let Some(range) = self
+            .range
+            .and_then(|key| MountainRangeMapper::new(key).to_mountain_range().ok())
+        {
+            search_conditions.push(SearchCondition {
+                search_type: SearchType::MountainRange,
+                value: range,
+            });
+        }
+        if let Some(park) = self
+            .park
+            .and_then(|key| ParkMapper::new(key).to_park().ok())
+        {
+            search_conditions.push(SearchCondition {
+                search_type: SearchType::Park,
+                value: park,
+            });
+        }
+        if let Some(mountain_name) = &self.name {
+            search_conditions.push(SearchCondition {
+                search_type: SearchType::Name,
+                value: mountain_name.to_string(),
+            });
+        }
+
+        search_conditions
+    }
+
+    pub fn filter_condition(&self) -> FilterCondition {
+        FilterCondition {
+            top_level_only: self.top_level_only,
+            volcano: self.volcano,
+            alert_level_min: self.alert_level_min,
+            in_season: self.in_season,
+            difficulty_max: self.difficulty_max,
+            primary_prefecture: self
+                .primary_prefecture
+                .and_then(|key| PrefectureMapper::new(key).to_prefecture().ok())
+                .map(|pref| pref.replace("Prefecture_", "")),
+        }
+    }
+
+    pub fn range_condition(&self) -> RangeCondition {
+        RangeCondition {
+            offset: self.offset,
+            limit: self.limit,
+        }
+    }
+}
+
+fn parse_prefecture(value: &str) -> Option<u32> {
+    let pref_key = value.parse::<u32>().ok()?;
+    PrefectureMapper::new(pref_key)
+        .to_prefecture()
+        .ok()
+        .map(|_| pref_key)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TechnicalGrade;
+    use std::collections::HashMap;
+
+    fn query_params(params: &[(&str, &str)]) -> StrMap {
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for (key, value) in params {
+            map.entry(key.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+        StrMap::from(map)
+    }
+
+    fn error_parameters(errors: &[ParameterError]) -> Vec<&str> {
+        errors
+            .iter()
+            .map(|error| error.parameter.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parse_empty_query() {
+        let query = MountainQuery::parse(&query_params(&[])).unwrap();
+        assert_eq!(query, MountainQuery::default());
+        assert!(query.search_conditions().is_empty());
+    }
+
+    #[test]
+    fn parse_valid_query() {
+        let query = MountainQuery::parse(&query_params(&[
+            ("prefecture", "20"),
+            ("tag", "1"),
+            ("name", "岳"),
+            ("top_level_only", "TRUE"),
+            ("primary_prefecture", "19"),
+            ("volcano", "false"),
+            ("alert_level_min", "2"),
+            ("in_season", "8"),
+            ("difficulty_max", "c"),
+            ("offset", "10"),
+            ("limit", "5"),
+            ("sort", "Elevation.Desc"),
+        ]))
+        .unwrap();
+
+        assert_eq!(query.prefecture, Some(20));
+        assert_eq!(query.tag, Some(1));
+        assert_eq!(query.name, Some("岳".to_string()));
+        assert!(query.top_level_only);
+        assert_eq!(query.volcano, Some(false));
+        assert_eq!(query.alert_level_min, Some(2));
+        assert_eq!(query.in_season, Some(8));
+        assert_eq!(
+            query.difficulty_max,
+            Some(DifficultyLimit {
+                technical: TechnicalGrade::C,
+                physical: None,
+            })
+        );
+        assert_eq!(query.offset, 10);
+        assert_eq!(query.limit, Some(5));
+        assert_eq!(query.sort, "elevation.desc");
+
+        let values: Vec<String> = query
+            .search_conditions()
+            .into_iter()
+            .map(|condition| condition.value)
+            .collect();
+        assert_eq!(values, vec!["Prefecture_長野県", "Tag_百名山", "岳"]);
+        assert_eq!(
+            query.filter_condition().primary_prefecture,
+            Some("山梨県".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_reports_non_numeric_ids() {
+        let errors = MountainQuery::parse(&query_params(&[("prefecture", "abc"), ("tag", "xyz")]))
+            .unwrap_err();
+        assert_eq!(error_parameters(&errors), vec!["prefecture", "tag"]);
+        assert_eq!(errors[0].message, Message::InvalidPrefectureId);
+        assert_eq!(errors[1].message, Message::InvalidTagId);
+    }
+
+    #[test]
+    fn park_is_parsed_into_search_condition() {
+        let query = MountainQuery::parse(&query_params(&[("park", "5")])).unwrap();
+        assert_eq!(query.park, Some(5));
+
+        let conditions = query.search_conditions();
+        assert_eq!(conditions.len(), 1);
+        assert!(matches!(conditions[0].search_type, SearchType::Park));
+        assert_eq!(conditions[0].value, "Park_大雪山国立公園");
+
+        for park in ["0", "36", "abc"] {
+            let errors = MountainQuery::parse(&query_params(&[("park", park)])).unwrap_err();
+            assert_eq!(
+                errors,
+                vec![ParameterError::new("park", Message::InvalidParkId)]
+            );
+        }
+    }
+
+    #[test]
+    fn parse_reports_every_invalid_parameter() {
+        let errors = MountainQuery::parse(&query_params(&[
+            ("prefecture", "48"),
+            ("range", "0"),
+            ("top_level_only", "yes"),
+            ("alert_level_min", "6"),
+            ("in_season", "13"),
+            ("difficulty_max", "F"),
+            ("offset", "-1"),
+            ("limit", "0"),
+            ("sort", "height"),
+        ]))
+        .unwrap_err();
+        assert_eq!(
+            error_parameters(&errors),
+            vec![
+                "prefecture",
+                "range",
+                "top_level_only",
+                "alert_level_min",
+                "in_season",
+                "difficulty_max",
+                "offset",
+                "limit",
+                "sort",
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_parameters_are_ignored_without_strict() {
+        let query = MountainQuery::parse(&query_params(&[("foo", "bar")])).unwrap();
+        assert!(!query.strict);
+    }
+
+    #[test]
+    fn strict_mode_rejects_unknown_parameters() {
+        let errors = MountainQuery::parse(&query_params(&[
+            ("strict", "true"),
+            ("format", "csv"),
+            ("lang", "en"),
+            ("prefectures", "20"),
+            ("foo", "bar"),
+        ]))
+        .unwrap_err();
+        assert_eq!(error_parameters(&errors), vec!["foo", "prefectures"]);
+        assert!(errors
+            .iter()
+            .all(|error| error.message == Message::UnknownParameter));
+    }
+
+    #[test]
+    fn parse_reports_invalid_strict() {
+        let errors = MountainQuery::parse(&query_params(&[("strict", "1")])).unwrap_err();
+        assert_eq!(error_parameters(&errors), vec!["strict"]);
+        assert_eq!(errors[0].message, Message::InvalidStrict);
+    }
+}
@@ -22,140 +22,151 @@ pub struct QueryFilterCommand {
     pub(crate) filter_value: String,
 }
 
-pub async fn scan_all(
-    client: &Client,
-    command: ScanCommand,
-) -> Result<Vec<HashMap<String, AttributeValue>>, ()> {
-    match client.scan().table_name(command.table).send().await {
-        Ok(resp) => {
-            match resp.items {
-                Some(items) => Ok(items),
-                _ => Err(()),
-            }
-            // if resp.count > 0 {
-            //     match resp.items {
-            //         Some(items) => Ok(items),
-            //         _ => Err(()),
-            //     }
-            // } else {
-            //     let empty_list: Vec<HashMap<String, AttributeValue>> = vec![];
-            //     Ok(empty_list)
-            // }
+type Item = HashMap<String, AttributeValue>;
+
+// 1回の応答は 1MB までのため、scan、query は LastEvaluatedKey がなくなるまで繰り返し取得する
+
+pub async fn scan_all(client: &Client, command: ScanCommand) -> Result<Vec<Item>, ()> {
+    let mut items: Vec<Item> = Vec::new();
+    let mut exclusive_start_key: Option<Item> = None;
+
+    loop {
+        let resp = client
+            .scan()
+            .table_name(command.table.to_string())
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await
+            .map_err(|_| ())?;
+
+        match resp.items {
+            Some(page) => items.extend(page),
+            _ => return Err(()),
+        }
+        match resp.last_evaluated_key {
+            Some(key) if !key.is_empty() => exclusive_start_key = Some(key),
+            _ => return Ok(items),
         }
-        Err(_) => Err(()),
     }
 }
 
-pub async fn query(
-    client: &Client,
-    command: QueryCommand,
-) -> Result<Vec<HashMap<String, AttributeValue>>, ()> {
+pub async fn query(client: &Client, command: QueryCommand) -> Result<Vec<Item>, ()> {
     let key = &command.key;
     let value = &command.value;
 
-    match client
-        .query()
-        .table_name(command.table)
-        .key_condition_expression("#key = :value".to_string())
-        .expression_attribute_names("#key".to_string(), key.to_string())
-        .expression_attribute_values(":value".to_string(), AttributeValue::N(value.to_string()))
-        .scan_index_forward(true)
-        .select(Select::AllAttributes)
-        .send()
-        .await
-    {
-        Ok(resp) => {
-            // match resp.items {
-            //     Some(items) => Ok(items),
-            //     _ => Err(()),
-            // }
-            if resp.count > 0 {
-                match resp.items {
-                    Some(items) => Ok(items),
-                    _ => Err(()),
-                }
-            } else {
-                Err(())
-            }
+    let mut items: Vec<Item> = Vec::new();
+    let mut exclusive_start_key: Option<Item> = None;
+
+    loop {
+        let resp = client
+            .query()
+            .table_name(command.table.to_string())
+            .key_condition_expression("#key = :value".to_string())
+            .expression_attribute_names("#key".to_string(), key.to_string())
+            .expression_attribute_values(":value".to_string(), AttributeValue::N(value.to_string()))
+            .scan_index_forward(true)
+            .select(Select::AllAttributes)
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await
+            .map_err(|_| ())?;
+
+        if let Some(page) = resp.items {
+            items.extend(page);
+        }
+        match resp.last_evaluated_key {
+            Some(key) if !key.is_empty() => exclusive_start_key = Some(key),
+            _ => break,
         }
-        Err(_) => Err(()),
     }
+
+    Ok(items)
 }
 
-pub async fn query_index(
-    client: &Client,
-    command: QueryCommand,
-) -> Result<Vec<HashMap<String, AttributeValue>>, ()> {
+pub async fn query_index(client: &Client, command: QueryCommand) -> Result<Vec<Item>, ()> {
     let key = &command.key;
     let value = &command.value;
 
     let mut index = String::new();
-    match command.index {
-        Some(param_index) => {
-            index = param_index;
-        }
-        _ => {}
+    if let Some(param_index) = command.index {
+        index = param_index;
     }
 
-    match client
-        .query()
-        .table_name(command.table)
-        .index_name(index)
-        .key_condition_expression("#key = :value".to_string())
-        .expression_attribute_names("#key".to_string(), key.to_string())
-        .expression_attribute_values(":value".to_string(), AttributeValue::S(value.to_string()))
-        .scan_index_forward(true)
-        .select(Select::AllAttributes)
-        .send()
-        .await
-    {
-        Ok(resp) => match resp.items {
-            Some(items) => Ok(items),
-            _ => Err(()),
-        },
-        Err(_) => Err(()),
+    let mut items: Vec<Item> = Vec::new();
+    let mut exclusive_start_key: Option<Item> = None;
+
+    loop {
+        let resp = client
+            .query()
+            .table_name(command.table.to_string())
+            .index_name(index.to_string())
+            .key_condition_expression("#key = :value".to_string())
+            .expression_attribute_names("#key".to_string(), key.to_string())
+            .expression_attribute_values(":value".to_string(), AttributeValue::S(value.to_string()))
+            .scan_index_forward(true)
+            .select(Select::AllAttributes)
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await
+            .map_err(|_| ())?;
+
+        match resp.items {
+            Some(page) => items.extend(page),
+            _ => return Err(()),
+        }
+        match resp.last_evaluated_key {
+            Some(key) if !key.is_empty() => exclusive_start_key = Some(key),
+            _ => return Ok(items),
+        }
     }
 }
 
 pub async fn query_index_filter(
     client: &Client,
     command: QueryFilterCommand,
-) -> Result<Vec<HashMap<String, AttributeValue>>, ()> {
+) -> Result<Vec<Item>, ()> {
     let key = &command.key;
     let value = &command.value;
     let filter_key = &command.filter_key;
     let filter_value = &command.filter_value;
 
     let mut index = String::new();
-    match command.index {
-        Some(param_index) => {
-            index = param_index;
-        }
-        _ => {}
+    if let Some(param_index) = command.index {
+        index = param_index;
     }
 
-    match client
-        .query()
-        .table_name(command.table)
-        .index_name(index)
-        .key_condition_expression("#key = :value".to_string())
-        .expression_attribute_names("#key".to_string(), key.to_string())
-        .expression_attribute_values(":value".to_string(), AttributeValue::S(value.to_string()))
-        .filter_expression("contains(#filterKey, :filterKey)".to_string())
-        .expression_attribute_names("#filterKey".to_string(), filter_key.to_string())
-        .expression_attribute_values(
-            ":filterKey".to_string(),
-            AttributeValue::S(filter_value.to_string()),
-        )
-        .scan_index_forward(true)
-        .select(Select::AllAttributes)
-        .send()
-        .await
-    {
-        Ok(resp) => match resp.items {
-            Some(items) => Ok(items),
-            _ => Err(()),
-        },
-        Err(_) => Err(()),
+    // FilterExpression は 1MB ごとの取得後に適用されるため、条件に一致しないページも続けて取得する
+    let mut items: Vec<Item> = Vec::new();
+    let mut exclusive_start_key: Option<Item> = None;
+
+    loop {
+        let resp = client
+            .query()
+            .table_name(command.table.to_string())
+            .index_name(index.to_string())
+            .key_condition_expression("#key = :value".to_string())
+            .expression_attribute_names("#key".to_string(), key.to_string())
+            .expression_attribute_values(":value".to_string(), AttributeValue::S(value.to_string()))
+            .filter_expression("contains(#filterKey, :filterKey)".to_string())
+            .expression_attribute_names("#filterKey".to_string(), filter_key.to_string())
+            .expression_attribute_values(
+                ":filterKey".to_string(),
+                AttributeValue::S(filter_value.to_string()),
+            )
+            .scan_index_forward(true)
+            .select(Select::AllAttributes)
+            .set_exclusive_start_key(exclusive_start_key)
+            .send()
+            .await
+            .map_err(|_| ())?;
+
+        match resp.items {
+            Some(page) => items.extend(page),
+            _ => return Err(()),
+        }
+        match resp.last_evaluated_key {
+            Some(key) if !key.is_empty() => exclusive_start_key = Some(key),
+            _ => return Ok(items),
+        }
     }
 }
@@ -0,0 +1,205 @@
+use crate::serializers::SerializedBody;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// この長さ（バイト）未満のボディは圧縮しない
+pub const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// brotli の圧縮品質（0〜11）とウィンドウサイズ
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW_SIZE: u32 = 22;
+
+/// レスポンスボディの圧縮形式
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ContentEncoding {
+    Brotli,
+    Gzip,
+}
+
+impl ContentEncoding {
+    pub fn header_value(&self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Gzip => "gzip",
+        }
+    }
+}
+
+/// Accept-Encoding ヘッダーから圧縮形式を選択する
+///
+/// q 値の高い形式を優先し、同じ q 値の場合は brotli を優先する。
+/// `*` は明示されていない形式をすべて受け入れるものとして扱う。
+pub fn negotiate_encoding(accept_encoding: Option<&str>) -> Option<ContentEncoding> {
+    let accept_encoding = accept_encoding?;
+
+    let mut brotli_quality: Option<f32> = None;
+    let mut gzip_quality: Option<f32> = None;
+    let mut wildcard_quality: Option<f32> = None;
+    for coding in accept_encoding.split(',') {
+        let mut parts = coding.split(';');
+        let name = parts.next().unwrap_or("").trim().to_lowercase();
+        let mut quality = 1.0;
+        for param in parts {
+            if let Some(q) = param.trim().strip_prefix("q=") {
+                quality = q.trim().parse::<f32>().unwrap_or(0.0);
+            }
+        }
+        match name.as_str() {
+            "br" => brotli_quality = Some(quality),
+            "gzip" | "x-gzip" => gzip_quality = Some(quality),
+            "*" => wildcard_quality = Some(quality),
+            _ => {}
+        }
+    }
+
+    let brotli_quality = brotli_quality.or(wildcard_quality).unwrap_or(0.0);
+    let gzip_quality = gzip_quality.or(wildcard_quality).unwrap_or(0.0);
+    if brotli_quality <= 0.0 && gzip_quality <= 0.0 {
+        None
+    } else if brotli_quality >= gzip_quality {
+        Some(ContentEncoding::Brotli)
+    } else {
+        Some(ContentEncoding::Gzip)
+    }
+}
+
+/// Accept-Encoding に応じてボディを圧縮する
+///
+/// 圧縮したボディは API Gateway が base64 で受け渡せるようにバイナリとして返す。
+/// 閾値未満、または圧縮に失敗した場合は元のボディをそのまま返す。
+pub fn encode_body(
+    body: SerializedBody,
+    accept_encoding: Option<&str>,
+) -> (SerializedBody, Option<ContentEncoding>) {
+    let encoding = match negotiate_encoding(accept_encoding) {
+        Some(encoding) => encoding,
+        None => return (body, None),
+    };
+
+    let bytes = match &body {
+        SerializedBody::Text(text) => text.as_bytes(),
+        SerializedBody::Binary(bytes) => bytes.as_slice(),
+    };
+    if bytes.len() < COMPRESSION_THRESHOLD {
+        return (body, None);
+    }
+
+    match compress(bytes, &encoding) {
+        Ok(compressed) => (SerializedBody::Binary(compressed), Some(encoding)),
+        Err(_) => (body, None),
+    }
+}
+
+/// 指定した形式で圧縮する
+#[allow(clippy::result_unit_err)]
+pub fn compress(bytes: &[u8], encoding: &ContentEncoding) -> Result<Vec<u8>, ()> {
+    match encoding {
+        ContentEncoding::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(bytes).map_err(|_| ())?;
+            encoder.finish().map_err(|_| ())
+        }
+        ContentEncoding::Brotli => {
+            let mut compressed = Vec::new();
+            {
+                let mut encoder = brotli::CompressorWriter::new(
+                    &mut compressed,
+                    4096,
+                    BROTLI_QUALITY,
+                    BROTLI_WINDOW_SIZE,
+                );
+                encoder.write_all(bytes).map_err(|_| ())?;
+                encoder.flush().map_err(|_| ())?;
+            }
+            Ok(compressed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn large_body() -> SerializedBody {
+        let mountains: Vec<String> = (1..=200)
+            .map(|id| format!(r#"{{"id": {}, "name": "山岳{}"}}"#, id, id))
+            .collect();
+        SerializedBody::Text(format!(r#"{{"mountains": [{}]}}"#, mountains.join(", ")))
+    }
+
+    fn decompress(bytes: &[u8], encoding: &ContentEncoding) -> Vec<u8> {
+        let mut decompressed = Vec::new();
+        match encoding {
+            ContentEncoding::Gzip => {
+                GzDecoder::new(bytes)
+                    .read_to_end(&mut decompressed)
+                    .unwrap();
+            }
+            ContentEncoding::Brotli => {
+                brotli::Decompressor::new(bytes, 4096)
+                    .read_to_end(&mut decompressed)
+                    .unwrap();
+            }
+        }
+        decompressed
+    }
+
+    #[test]
+    fn gzip_round_trip() {
+        let body = large_body();
+        let (encoded, encoding) = encode_body(body.clone(), Some("gzip, deflate"));
+        assert_eq!(encoding, Some(ContentEncoding::Gzip));
+        match (encoded, body) {
+            (SerializedBody::Binary(bytes), SerializedBody::Text(text)) => {
+                assert!(bytes.len() < text.len());
+                assert_eq!(
+                    decompress(&bytes, &ContentEncoding::Gzip),
+                    text.into_bytes()
+                );
+            }
+            _ => panic!("圧縮後のボディがバイナリではありません"),
+        }
+    }
+
+    #[test]
+    fn brotli_round_trip() {
+        let body = SerializedBody::Binary(vec![0xa5; COMPRESSION_THRESHOLD * 4]);
+        let (encoded, encoding) = encode_body(body.clone(), Some("gzip, br"));
+        assert_eq!(encoding, Some(ContentEncoding::Brotli));
+        match (encoded, body) {
+            (SerializedBody::Binary(compressed), SerializedBody::Binary(original)) => {
+                assert_eq!(decompress(&compressed, &ContentEncoding::Brotli), original);
+            }
+            _ => panic!("圧縮後のボディがバイナリではありません"),
+        }
+    }
+
+    #[test]
+    fn small_body_is_not_compressed() {
+        let body =
+            SerializedBody::Text(r#"{"message": "山岳情報が見つかりませんでした。"}"#.to_string());
+        assert_eq!(encode_body(body.clone(), Some("br, gzip")), (body, None));
+    }
+
+    #[test]
+    fn negotiate_encoding_with_quality() {
+        assert_eq!(negotiate_encoding(None), None);
+        assert_eq!(negotiate_encoding(Some("identity")), None);
+        assert_eq!(
+            negotiate_encoding(Some("br;q=0, gzip")),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            negotiate_encoding(Some("br;q=0.5, gzip;q=0.8")),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(negotiate_encoding(Some("*")), Some(ContentEncoding::Brotli));
+        assert_eq!(
+            negotiate_encoding(Some("gzip, *;q=0")),
+            Some(ContentEncoding::Gzip)
+        );
+    }
+}
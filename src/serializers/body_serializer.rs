@@ -0,0 +1,376 @@
+use serde::Serialize;
+
+/// レスポンスボディの形式
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BodyFormat {
+    Json { pretty: bool },
+    GeoJson,
+    Csv,
+    Gpx,
+    Kml,
+    MessagePack,
+    Cbor,
+}
+
+/// シリアライズしたレスポンスボディ
+#[derive(Debug, PartialEq, Clone)]
+pub enum SerializedBody {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// 形式の選択に失敗した理由
+#[derive(Debug, PartialEq)]
+pub enum NegotiationError {
+    /// format パラメータ、Accept ヘッダーに対応する形式がない（406）
+    NotAcceptable,
+    /// pretty パラメータが不正（400）
+    InvalidPretty,
+}
+
+/// 全てのエンドポイントで利用できる形式
+pub const DATA_FORMATS: [BodyFormat; 3] = [
+    BodyFormat::Json { pretty: true },
+    BodyFormat::MessagePack,
+    BodyFormat::Cbor,
+];
+
+impl BodyFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            BodyFormat::Json { .. } => "application/json",
+            BodyFormat::GeoJson => "application/geo+json",
+            BodyFormat::Csv => "text/csv; charset=utf-8",
+            BodyFormat::Gpx => "application/gpx+xml",
+            BodyFormat::Kml => "application/vnd.google-earth.kml+xml",
+            BodyFormat::MessagePack => "application/msgpack",
+            BodyFormat::Cbor => "application/cbor",
+        }
+    }
+
+    /// ファイルとしてダウンロードさせる形式の Content-Disposition
+    pub fn content_disposition(&self) -> Option<&'static str> {
+        match self {
+            BodyFormat::Csv => Some(r#"attachment; filename="mountains.csv""#),
+            BodyFormat::Gpx => Some(r#"attachment; filename="mountains.gpx""#),
+            BodyFormat::Kml => Some(r#"attachment; filename="mountains.kml""#),
+            _ => None,
+        }
+    }
+
+    /// format パラメータに指定する名前
+    pub fn format_param(&self) -> &'static str {
+        match self {
+            BodyFormat::Json { .. } => "json",
+            BodyFormat::GeoJson => "geojson",
+            BodyFormat::Csv => "csv",
+            BodyFormat::Gpx => "gpx",
+            BodyFormat::Kml => "kml",
+            BodyFormat::MessagePack => "msgpack",
+            BodyFormat::Cbor => "cbor",
+        }
+    }
+
+    fn from_format_param(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "json" => Some(BodyFormat::Json { pretty: true }),
+            "geojson" => Some(BodyFormat::GeoJson),
+            "csv" => Some(BodyFormat::Csv),
+            "gpx" => Some(BodyFormat::Gpx),
+            "kml" => Some(BodyFormat::Kml),
+            "msgpack" | "messagepack" => Some(BodyFormat::MessagePack),
+            "cbor" => Some(BodyFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/json" => Some(BodyFormat::Json { pretty: true }),
+            "application/geo+json" => Some(BodyFormat::GeoJson),
+            "text/csv" => Some(BodyFormat::Csv),
+            "application/gpx+xml" => Some(BodyFormat::Gpx),
+            "application/vnd.google-earth.kml+xml" => Some(BodyFormat::Kml),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(BodyFormat::MessagePack)
+            }
+            "application/cbor" => Some(BodyFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    /// pretty の指定を無視して同じ形式か判定する
+    fn is_same_kind(&self, other: &BodyFormat) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// format パラメータ、Accept ヘッダーの順にレスポンスの形式を選択する
+///
+/// Accept ヘッダーは形式ごとに最も具体的なメディアレンジの q 値で評価する。
+/// `*/*` などのワイルドカードは allowed_formats のうち q=0 で除外されていない
+/// 先頭の形式に一致する。
+pub fn negotiate_format(
+    format_param: Option<&str>,
+    accept: Option<&str>,
+    pretty_param: Option<&str>,
+    allowed_formats: &[BodyFormat],
+) -> Result<BodyFormat, NegotiationError> {
+    let pretty = match pretty_param.map(|p| p.to_lowercase()) {
+        None => true,
+        Some(p) if p == "true" => true,
+        Some(p) if p == "false" => false,
+        Some(_) => return Err(NegotiationError::InvalidPretty),
+    };
+
+    let format = match format_param {
+        Some(format) => match BodyFormat::from_format_param(format) {
+            Some(format) => allowed(format, allowed_formats),
+            None => None,
+        },
+        None => match accept {
+            Some(accept) if !accept.trim().is_empty() => accept_format(accept, allowed_formats),
+            _ => allowed_formats.first().copied(),
+        },
+    };
+
+    match format {
+        Some(BodyFormat::Json { .. }) => Ok(BodyFormat::Json { pretty }),
+        Some(format) => Ok(format),
+        None => Err(NegotiationError::NotAcceptable),
+    }
+}
+
+fn allowed(format: BodyFormat, allowed_formats: &[BodyFormat]) -> Option<BodyFormat> {
+    if allowed_formats.iter().any(|f| f.is_same_kind(&format)) {
+        Some(format)
+    } else {
+        None
+    }
+}
+
+fn accept_format(accept: &str, allowed_formats: &[BodyFormat]) -> Option<BodyFormat> {
+    let mut media_ranges: Vec<(String, f32)> = Vec::new();
+    for media_range in accept.split(',') {
+        let mut parts = media_range.split(';');
+        let media_type = parts.next().unwrap_or("").trim().to_lowercase();
+        let mut quality = 1.0;
+        for param in parts {
+            if let Some(q) = param.trim().strip_prefix("q=") {
+                quality = q.trim().parse::<f32>().unwrap_or(0.0);
+            }
+        }
+        // q=0 は明示的な除外として保持する
+        if !media_type.is_empty() {
+            media_ranges.push((media_type, quality));
+        }
+    }
+
+    // q 値の高い形式を選び、同じ q 値の場合は Accept の記述順、allowed_formats の順を優先する
+    let mut best: Option<(f32, usize, BodyFormat)> = None;
+    for format in allowed_formats {
+        if let Some((quality, index)) = format_quality(format, &media_ranges) {
+            let is_better = match best {
+                None => true,
+                Some((best_quality, best_index, _)) => {
+                    quality > best_quality || (quality == best_quality && index < best_index)
+                }
+            };
+            if quality > 0.0 && is_better {
+                best = Some((quality, index, *format));
+            }
+        }
+    }
+
+    best.map(|(_, _, format)| format)
+}
+
+/// 形式に一致する最も具体的なメディアレンジの q 値と記述位置を返す
+///
+/// `application/json` のような完全一致、`application/*`、`*/*` の順に優先する。
+fn format_quality(format: &BodyFormat, media_ranges: &[(String, f32)]) -> Option<(f32, usize)> {
+    let main_type = format.content_type().split('/').next().unwrap_or("");
+    let mut matched: Option<(u8, f32, usize)> = None;
+    for (index, (media_type, quality)) in media_ranges.iter().enumerate() {
+        let specificity =
+            if BodyFormat::from_media_type(media_type).is_some_and(|f| f.is_same_kind(format)) {
+                2
+            } else if media_type.strip_suffix("/*") == Some(main_type) {
+                1
+            } else if media_type == "*/*" {
+                0
+            } else {
+                continue;
+            };
+        if matched.is_none_or(|(s, _, _)| specificity > s) {
+            matched = Some((specificity, *quality, index));
+        }
+    }
+
+    matched.map(|(_, quality, index)| (quality, index))
+}
+
+/// JSON、MessagePack、CBOR のいずれかでシリアライズする
+///
+/// CSV、GPX、KML は山岳一覧専用の形式のため、Err を返す。
+#[allow(clippy::result_unit_err)]
+pub fn serialize_body<T: Serialize>(value: &T, format: &BodyFormat) -> Result<SerializedBody, ()> {
+    match format {
+        BodyFormat::Json { pretty: true } | BodyFormat::GeoJson => {
+            serde_json::to_string_pretty(value)
+                .map(SerializedBody::Text)
+                .map_err(|_| ())
+        }
+        BodyFormat::Json { pretty: false } => serde_json::to_string(value)
+            .map(SerializedBody::Text)
+            .map_err(|_| ()),
+        BodyFormat::MessagePack => rmp_serde::to_vec_named(value)
+            .map(SerializedBody::Binary)
+            .map_err(|_| ()),
+        BodyFormat::Cbor => serde_cbor::to_vec(value)
+            .map(SerializedBody::Binary)
+            .map_err(|_| ()),
+        BodyFormat::Csv | BodyFormat::Gpx | BodyFormat::Kml => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTAIN_LIST_FORMATS: [BodyFormat; 7] = [
+        BodyFormat::Json { pretty: true },
+        BodyFormat::GeoJson,
+        BodyFormat::Csv,
+        BodyFormat::Gpx,
+        BodyFormat::Kml,
+        BodyFormat::MessagePack,
+        BodyFormat::Cbor,
+    ];
+
+    fn negotiate(accept: &str) -> Result<BodyFormat, NegotiationError> {
+        negotiate_format(None, Some(accept), None, &MOUNTAIN_LIST_FORMATS)
+    }
+
+    #[test]
+    fn defaults_to_first_format() {
+        assert_eq!(
+            negotiate_format(None, None, None, &MOUNTAIN_LIST_FORMATS),
+            Ok(BodyFormat::Json { pretty: true })
+        );
+        assert_eq!(negotiate(" "), Ok(BodyFormat::Json { pretty: true }));
+        assert_eq!(
+            negotiate_format(None, None, Some("false"), &MOUNTAIN_LIST_FORMATS),
+            Ok(BodyFormat::Json { pretty: false })
+        );
+        assert_eq!(
+            negotiate_format(None, None, Some("yes"), &MOUNTAIN_LIST_FORMATS),
+            Err(NegotiationError::InvalidPretty)
+        );
+    }
+
+    #[test]
+    fn prefers_higher_quality() {
+        assert_eq!(
+            negotiate("application/json;q=0.5, application/cbor"),
+            Ok(BodyFormat::Cbor)
+        );
+        assert_eq!(
+            negotiate("text/csv;q=0.8, application/gpx+xml;q=0.9"),
+            Ok(BodyFormat::Gpx)
+        );
+        // 同じ q 値の場合は記述順
+        assert_eq!(
+            negotiate("application/msgpack, application/geo+json"),
+            Ok(BodyFormat::MessagePack)
+        );
+        // ワイルドカードが先に書かれていても q 値の高い形式を選ぶ
+        assert_eq!(
+            negotiate("*/*;q=0.1, application/cbor;q=0.9"),
+            Ok(BodyFormat::Cbor)
+        );
+    }
+
+    #[test]
+    fn wildcards_match_allowed_formats() {
+        assert_eq!(negotiate("*/*"), Ok(BodyFormat::Json { pretty: true }));
+        assert_eq!(
+            negotiate("application/*"),
+            Ok(BodyFormat::Json { pretty: true })
+        );
+        assert_eq!(negotiate("text/*"), Ok(BodyFormat::Csv));
+        assert_eq!(negotiate("image/*"), Err(NegotiationError::NotAcceptable));
+        assert_eq!(
+            negotiate_format(None, Some("*/*"), None, &DATA_FORMATS[1..]),
+            Ok(BodyFormat::MessagePack)
+        );
+    }
+
+    #[test]
+    fn zero_quality_excludes_format() {
+        assert_eq!(
+            negotiate("application/json;q=0, */*"),
+            Ok(BodyFormat::GeoJson)
+        );
+        assert_eq!(
+            negotiate("application/json;q=0, application/geo+json;q=0, application/*"),
+            Ok(BodyFormat::Gpx)
+        );
+        // 具体的なメディアレンジの q 値がワイルドカードより優先する
+        assert_eq!(
+            negotiate("application/json, */*;q=0"),
+            Ok(BodyFormat::Json { pretty: true })
+        );
+        assert_eq!(
+            negotiate_format(
+                None,
+                Some("application/json;q=0, */*"),
+                None,
+                &[BodyFormat::Json { pretty: true }]
+            ),
+            Err(NegotiationError::NotAcceptable)
+        );
+        assert_eq!(negotiate("*/*;q=0"), Err(NegotiationError::NotAcceptable));
+    }
+
+    #[test]
+    fn format_param_overrides_accept() {
+        assert_eq!(
+            negotiate_format(
+                Some("CSV"),
+                Some("application/json"),
+                None,
+                &MOUNTAIN_LIST_FORMATS
+            ),
+            Ok(BodyFormat::Csv)
+        );
+        assert_eq!(
+            negotiate_format(
+                Some("json"),
+                Some("application/cbor"),
+                Some("false"),
+                &DATA_FORMATS
+            ),
+            Ok(BodyFormat::Json { pretty: false })
+        );
+        assert_eq!(
+            negotiate_format(Some("csv"), Some("*/*"), None, &DATA_FORMATS),
+            Err(NegotiationError::NotAcceptable)
+        );
+        assert_eq!(
+            negotiate_format(Some("xml"), None, None, &DATA_FORMATS),
+            Err(NegotiationError::NotAcceptable)
+        );
+    }
+
+    #[test]
+    fn serialize_body_rejects_document_formats() {
+        assert_eq!(
+            serialize_body(&vec![1, 2], &BodyFormat::Json { pretty: false }),
+            Ok(SerializedBody::Text("[1,2]".to_string()))
+        );
+        assert_eq!(serialize_body(&vec![1, 2], &BodyFormat::Csv), Err(()));
+        assert_eq!(serialize_body(&vec![1, 2], &BodyFormat::Gpx), Err(()));
+        assert_eq!(serialize_body(&vec![1, 2], &BodyFormat::Kml), Err(()));
+    }
+}
@@ -0,0 +1,5 @@
+mod body_encoder;
+mod body_serializer;
+
+pub use body_encoder::*;
+pub use body_serializer::*;
@@ -0,0 +1,229 @@
+use crate::i18n::Message;
+use crate::models::{to_direction, Mountain, ParameterError};
+use crate::services::{attach_sub_peaks, find_mountain_by_id, ServiceError};
+use aws_sdk_dynamodb::Client;
+use lambda_http::StrMap;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// 2つの山岳の差分（from から見た to の値）
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MountainDifference {
+    pub from: u32,
+    pub to: u32,
+    pub elevation_difference: f64,
+    pub distance: f64,
+    pub bearing: f64,
+    pub direction: String,
+    pub shared_prefectures: Vec<String>,
+    pub shared_tags: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparedMountainResult {
+    pub mountains: Vec<Mountain>,
+    pub differences: Vec<MountainDifference>,
+}
+
+/// ids（カンマ区切りの2件から3件の山岳ID）を検証する
+pub fn compare_ids(query_params: &StrMap) -> Result<Vec<String>, Vec<ParameterError>> {
+    let ids = match query_params.get("ids") {
+        Some(ids) => ids,
+        None => return Err(vec![ParameterError::new("ids", Message::InvalidIds)]),
+    };
+
+    let mut id_list: Vec<String> = Vec::new();
+    for id in ids.split(',') {
+        match id.trim().parse::<u32>() {
+            Ok(id_value) => id_list.push(id_value.to_string()),
+            Err(_) => return Err(vec![ParameterError::new("ids", Message::InvalidMountainId)]),
+        }
+    }
+
+    if id_list.len() < 2 || id_list.len() > 3 {
+        return Err(vec![ParameterError::new("ids", Message::InvalidIds)]);
+    }
+
+    for (i, id) in id_list.iter().enumerate() {
+        if id_list[i + 1..].contains(id) {
+            return Err(vec![ParameterError::new(
+                "ids",
+                Message::DuplicatedMountainId,
+            )]);
+        }
+    }
+
+    Ok(id_list)
+}
+
+pub async fn compare_mountains(
+    client: &Client,
+    ids: Vec<String>,
+) -> Result<ComparedMountainResult, ServiceError> {
+    let mut mountains: Vec<Mountain> = Vec::new();
+    for id in ids {
+        mountains.push(find_mountain_by_id(client, id).await?);
+    }
+    attach_sub_peaks(client, &mut mountains).await?;
+
+    Ok(ComparedMountainResult {
+        differences: differences(&mountains),
+        mountains,
+    })
+}
+
+/// 指定順に全ての組み合わせを比較する
+fn differences(mountains: &[Mountain]) -> Vec<MountainDifference> {
+    let mut differences: Vec<MountainDifference> = Vec::new();
+    for (i, from) in mountains.iter().enumerate() {
+        for to in &mountains[i + 1..] {
+            differences.push(difference(from, to));
+        }
+    }
+
+    differences
+}
+
+fn difference(from: &Mountain, to: &Mountain) -> MountainDifference {
+    let bearing = from.location.bearing_to(&to.location);
+
+    MountainDifference {
+        from: from.id,
+        to: to.id,
+        elevation_difference: to.elevation - from.elevation,
+        distance: from.location.distance_to(&to.location),
+        bearing,
+        direction: to_direction(bearing),
+        shared_prefectures: shared_values(&from.prefectures, &to.prefectures),
+        shared_tags: shared_values(&from.tags, &to.tags),
+    }
+}
+
+fn shared_values(base: &[String], target: &[String]) -> Vec<String> {
+    base.iter()
+        .filter(|value| target.contains(value))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Location;
+    use std::collections::HashMap;
+
+    fn ids_param(ids: &str) -> StrMap {
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        map.insert("ids".to_string(), vec![ids.to_string()]);
+        StrMap::from(map)
+    }
+
+    fn ids_error(message: Message) -> Result<Vec<String>, Vec<ParameterError>> {
+        Err(vec![ParameterError::new("ids", message)])
+    }
+
+    #[test]
+    fn parse_compare_ids() {
+        assert_eq!(
+            compare_ids(&ids_param("1,2")),
+            Ok(vec!["1".to_string(), "2".to_string()])
+        );
+        // 前後の空白と先頭の0は取り除く
+        assert_eq!(
+            compare_ids(&ids_param(" 3, 02 ,1")),
+            Ok(vec!["3".to_string(), "2".to_string(), "1".to_string()])
+        );
+    }
+
+    #[test]
+    fn compare_ids_must_be_two_to_three() {
+        assert_eq!(
+            compare_ids(&StrMap::default()),
+            ids_error(Message::InvalidIds)
+        );
+        assert_eq!(compare_ids(&ids_param("1")), ids_error(Message::InvalidIds));
+        assert_eq!(
+            compare_ids(&ids_param("1,2,3,4")),
+            ids_error(Message::InvalidIds)
+        );
+    }
+
+    #[test]
+    fn compare_ids_must_be_numeric_and_unique() {
+        assert_eq!(
+            compare_ids(&ids_param("1,abc")),
+            ids_error(Message::InvalidMountainId)
+        );
+        assert_eq!(
+            compare_ids(&ids_param("1,")),
+            ids_error(Message::InvalidMountainId)
+        );
+        assert_eq!(
+            compare_ids(&ids_param("1,-2")),
+            ids_error(Message::InvalidMountainId)
+        );
+        assert_eq!(
+            compare_ids(&ids_param("1,2,01")),
+            ids_error(Message::DuplicatedMountainId)
+        );
+    }
+
+    fn mountain(id: u32, elevation: f64, latitude: f64, prefectures: &[&str]) -> Mountain {
+        Mountain {
+            id,
+            elevation,
+            location: Location {
+                latitude,
+                longitude: 138.0,
+                gsi_url: "".to_string(),
+            },
+            prefectures: prefectures.iter().map(|p| p.to_string()).collect(),
+            tags: vec!["百名山".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn differences_are_seen_from_first_mountain() {
+        let mountains = vec![
+            mountain(1, 3000.0, 35.0, &["長野県", "岐阜県"]),
+            mountain(2, 2500.0, 35.1, &["岐阜県", "富山県"]),
+        ];
+        let differences = differences(&mountains);
+        assert_eq!(differences.len(), 1);
+
+        let difference = &differences[0];
+        assert_eq!((difference.from, difference.to), (1, 2));
+        assert_eq!(difference.elevation_difference, -500.0);
+        // 緯度 0.1 度は約 11.12km、真北
+        assert!((difference.distance - 11.12).abs() < 0.01);
+        assert!(difference.bearing.abs() < 1e-9);
+        assert_eq!(difference.direction, "N");
+        assert_eq!(difference.shared_prefectures, vec!["岐阜県"]);
+        assert_eq!(difference.shared_tags, vec!["百名山"]);
+    }
+
+    #[test]
+    fn every_pair_is_compared_in_order() {
+        let mountains = vec![
+            mountain(3, 1000.0, 35.0, &[]),
+            mountain(1, 2000.0, 34.9, &[]),
+            mountain(2, 1500.0, 35.05, &[]),
+        ];
+        let pairs: Vec<(u32, u32, f64, String)> = differences(&mountains)
+            .into_iter()
+            .map(|d| (d.from, d.to, d.elevation_difference, d.direction))
+            .collect();
+
+        assert_eq!(
+            pairs,
+            vec![
+                (3, 1, 1000.0, "S".to_string()),
+                (3, 2, 500.0, "N".to_string()),
+                (1, 2, -500.0, "N".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::models::{Hut, HutBaseMapper, HutMapper, Location, HUT_TABLE};
+use crate::repositories::{query, scan_all, QueryCommand, ScanCommand};
+use crate::services::{find_mountain_by_id, ServiceError};
+use aws_sdk_dynamodb::Client;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::cmp::Ordering;
+
+pub struct NearbyHutCondition {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius: f64,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NearbyHut {
+    #[serde(flatten)]
+    pub hut: Hut,
+    pub distance: f64,
+}
+
+pub async fn get_all_huts(client: &Client) -> Result<Vec<Hut>, ServiceError> {
+    let command = ScanCommand {
+        table: HUT_TABLE.to_string(),
+    };
+
+    match scan_all(client, command).await {
+        Ok(response) => {
+            let mut huts: Vec<Hut> = Vec::new();
+            for item in response {
+                let mapper = HutMapper::new(item);
+                huts.push(mapper.to_hut());
+            }
+            huts.sort_by_key(|hut| hut.id);
+
+            Ok(huts)
+        }
+        Err(_) => Err(ServiceError::query_failed(HUT_TABLE)),
+    }
+}
+
+pub async fn get_hut_by_id(client: &Client, id: String) -> Result<Hut, ServiceError> {
+    // 数値でない山小屋IDは query がエラーとなるため、存在しないものとする
+    if id.parse::<u32>().is_err() {
+        return Err(ServiceError::NotFound);
+    }
+
+    let command = QueryCommand {
+        table: HUT_TABLE.to_string(),
+        index: None,
+        key: "Id".to_string(),
+        value: id,
+    };
+
+    match query(client, command).await {
+        Ok(response) => match response.into_iter().next() {
+            Some(item) => {
+                let mapper = HutMapper::new(item);
+                Ok(mapper.to_hut())
+            }
+            None => Err(ServiceError::NotFound),
+        },
+        Err(_) => Err(ServiceError::query_failed(HUT_TABLE)),
+    }
+}
+
+pub async fn get_huts_by_mountain_id(
+    client: &Client,
+    id: String,
+) -> Result<Vec<Hut>, ServiceError> {
+    // 存在しない山岳の場合はエラーとする
+    let mountain = find_mountain_by_id(client, id).await?;
+
+    let mut huts = get_all_huts(client).await?;
+    huts.retain(|hut| hut.mountains.contains(&mountain.id));
+
+    Ok(huts)
+}
+
+pub async fn get_nearby_huts(
+    client: &Client,
+    nearby_condition: NearbyHutCondition,
+) -> Result<Vec<NearbyHut>, ServiceError> {
+    let huts = get_all_huts(client).await?;
+    Ok(find_nearby_huts(huts, &nearby_condition))
+}
+
+/// 指定した座標から半径内の山小屋を距離の近い順に並べる
+fn find_nearby_huts(huts: Vec<Hut>, nearby_condition: &NearbyHutCondition) -> Vec<NearbyHut> {
+    let origin = Location {
+        latitude: nearby_condition.latitude,
+        longitude: nearby_condition.longitude,
+        gsi_url: "".to_string(),
+    };
+
+    let mut nearby_huts: Vec<NearbyHut> = Vec::new();
+    for hut in huts {
+        let distance = origin.distance_to(&hut.location);
+        if distance <= nearby_condition.radius {
+            nearby_huts.push(NearbyHut { hut, distance });
+        }
+    }
+
+    // 距離の近い順
+    nearby_huts.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(Ordering::Equal)
+            .then(a.hut.id.cmp(&b.hut.id))
+    });
+
+    nearby_huts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_dynamodb::model::AttributeValue;
+    use std::collections::HashMap;
+
+    fn hut(id: u32, latitude: f64, longitude: f64) -> Hut {
+        let mut location: HashMap<String, AttributeValue> = HashMap::new();
+        location.insert(
+            "Latitude".to_string(),
+            AttributeValue::N(latitude.to_string()),
+        );
+        location.insert(
+            "Longitude".to_string(),
+            AttributeValue::N(longitude.to_string()),
+        );
+
+        let mut item: HashMap<String, AttributeValue> = HashMap::new();
+        item.insert("Id".to_string(), AttributeValue::N(id.to_string()));
+        item.insert("LocationValue".to_string(), AttributeValue::M(location));
+        HutMapper::new(item).to_hut()
+    }
+
+    fn nearby_hut_ids(huts: Vec<Hut>, radius: f64) -> Vec<(u32, f64)> {
+        let condition = NearbyHutCondition {
+            latitude: 35.0,
+            longitude: 138.0,
+            radius,
+        };
+        find_nearby_huts(huts, &condition)
+            .iter()
+            .map(|nearby_hut| (nearby_hut.hut.id, nearby_hut.distance.round()))
+            .collect()
+    }
+
+    #[test]
+    fn nearby_huts_are_within_radius_and_sorted_by_distance() {
+        // 緯度 0.1 度は約 11km
+        let huts = vec![
+            hut(1, 35.2, 138.0),
+            hut(2, 35.1, 138.0),
+            hut(3, 34.9, 138.0),
+            hut(4, 36.0, 138.0),
+        ];
+
+        // 同じ距離の山小屋はID順
+        assert_eq!(
+            nearby_hut_ids(huts.clone(), 20.0),
+            vec![(2, 11.0), (3, 11.0)]
+        );
+        assert_eq!(
+            nearby_hut_ids(huts.clone(), 30.0),
+            vec![(2, 11.0), (3, 11.0), (1, 22.0)]
+        );
+        assert!(nearby_hut_ids(huts, 5.0).is_empty());
+    }
+
+    #[test]
+    fn hut_on_radius_boundary_is_included() {
+        let origin = hut(0, 35.0, 138.0);
+        let boundary = hut(1, 35.0, 138.1);
+        let radius = origin.location.distance_to(&boundary.location);
+
+        assert_eq!(nearby_hut_ids(vec![boundary.clone()], radius).len(), 1);
+        assert!(nearby_hut_ids(vec![boundary], radius - 0.001).is_empty());
+    }
+}
@@ -1,3 +1,15 @@
+mod compare_service;
+mod hut_service;
 mod mountain_service;
+mod park_service;
+mod rank_service;
+mod route_service;
+mod service_error;
 
+pub use compare_service::*;
+pub use hut_service::*;
 pub use mountain_service::*;
+pub use park_service::*;
+pub use rank_service::*;
+pub use route_service::*;
+pub use service_error::*;
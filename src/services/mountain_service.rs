@@ -1,15 +1,24 @@
-use crate::models::{get_value, Mountain, MountainBaseMapper, MountainMapper, ValueType};
+use crate::models::{
+    alias_matches, get_aliases, get_value, to_direction, DifficultyLimit, Mountain,
+    MountainBaseMapper, MountainMapper, ValueType,
+};
 use crate::repositories::{
     query, query_index, query_index_filter, scan_all, QueryCommand, QueryFilterCommand, ScanCommand,
 };
+use crate::services::ServiceError;
 use aws_sdk_dynamodb::model::AttributeValue;
 use aws_sdk_dynamodb::Client;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
 
 pub enum SearchType {
     Name,
     Prefecture,
     Tag,
+    MountainRange,
+    Park,
 }
 
 pub struct SearchCondition {
@@ -17,6 +26,17 @@ pub struct SearchCondition {
     pub value: String,
 }
 
+/// 取得後の山岳に対して適用する絞り込み条件
+#[derive(Default)]
+pub struct FilterCondition {
+    pub top_level_only: bool,
+    pub volcano: Option<bool>,
+    pub alert_level_min: Option<u8>,
+    pub in_season: Option<u32>,
+    pub difficulty_max: Option<DifficultyLimit>,
+    pub primary_prefecture: Option<String>,
+}
+
 pub struct RangeCondition {
     pub offset: usize,
     pub limit: Option<usize>,
@@ -34,77 +54,130 @@ pub struct SearchedMountainResult {
     pub limit: Option<usize>,
 }
 
+pub struct NearbyCondition {
+    pub radius: f64,
+    pub limit: usize,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NearbyMountain {
+    #[serde(flatten)]
+    pub mountain: Mountain,
+    pub distance: f64,
+    pub bearing: f64,
+    pub direction: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NearbyMountainResult {
+    pub mountains: Vec<NearbyMountain>,
+    pub total: usize,
+    pub radius: f64,
+    pub limit: usize,
+}
+
 pub async fn get_all_mountains(
     client: &Client,
+    filter_condition: FilterCondition,
     range_condition: RangeCondition,
-    sort_key: &String,
-) -> Result<SearchedMountainResult, ()> {
+    sort_key: &str,
+) -> Result<SearchedMountainResult, ServiceError> {
+    let mut mountains = scan_mountains(client).await?;
+    filter_mountains(&mut mountains, &filter_condition);
+
+    // sorting
+    if !mountains.is_empty() {
+        sort_mountains(&mut mountains, sort_key);
+    }
+
+    // offset, limitによる絞り込み
+    let refined_mountain_result = refine_mountains(&mountains, range_condition)?;
+    Ok(SearchedMountainResult {
+        mountains: refined_mountain_result.mountains,
+        total: refined_mountain_result.total,
+        offset: refined_mountain_result.offset,
+        limit: refined_mountain_result.limit,
+    })
+}
+
+pub(crate) async fn scan_mountains(client: &Client) -> Result<Vec<Mountain>, ServiceError> {
     let command = ScanCommand {
         table: "Mountains".to_string(),
     };
 
     match scan_all(client, command).await {
         Ok(response) => {
-            // id毎に切り分けたデータを格納する
-            let mut mountain_data_list: Vec<MountainData> = Vec::new();
-            for item in response {
-                match item.get("Id") {
-                    Some(attr_value) => match attr_value.as_n() {
-                        Ok(id) => {
-                            let mut is_duplicated = false;
-                            let mut target_index = 0u32;
-                            for m_data in &mountain_data_list {
-                                if m_data.index == id.to_string() {
-                                    is_duplicated = true;
-                                    break;
-                                }
-                                target_index = target_index + 1;
-                            }
-
-                            if !is_duplicated {
-                                mountain_data_list.push(MountainData {
-                                    index: id.to_string(),
-                                    attribute_data_list: vec![item],
-                                });
-                            } else {
-                                mountain_data_list[target_index as usize]
-                                    .attribute_data_list
-                                    .push(item);
-                            }
-                        }
-                        Err(_) => {}
-                    },
-                    _ => {}
-                }
-            }
-
             let mut mountains: Vec<Mountain> = Vec::new();
-            for mountain_data in mountain_data_list {
-                let mapper = MountainMapper::new(mountain_data.attribute_data_list);
-                mountains.push(mapper.to_mountain());
+            for attribute_data_list in group_by_id(response) {
+                let mapper = MountainMapper::new(attribute_data_list);
+                mountains.push(mapper.to_mountain().map_err(ServiceError::Internal)?);
             }
+            link_sub_peaks(&mut mountains);
 
-            // sorting
-            if mountains.len() > 0 {
-                sort_mountains(&mut mountains, sort_key);
-            }
+            Ok(mountains)
+        }
+        Err(_) => Err(ServiceError::query_failed("Mountains")),
+    }
+}
 
-            // offset, limitによる絞り込み
-            match refine_mountains(&mountains, range_condition) {
-                Ok(refined_mountain_result) => Ok(SearchedMountainResult {
-                    mountains: refined_mountain_result.mountains,
-                    total: refined_mountain_result.total,
-                    offset: refined_mountain_result.offset,
-                    limit: refined_mountain_result.limit,
-                }),
-                Err(_) => Err(()),
+/// scanした項目をid毎に切り分ける
+fn group_by_id(
+    items: Vec<HashMap<String, AttributeValue>>,
+) -> Vec<Vec<HashMap<String, AttributeValue>>> {
+    // id毎に切り分けたデータを格納する
+    let mut mountain_data_list: Vec<MountainData> = Vec::new();
+    for item in items {
+        if let Some(attr_value) = item.get("Id") {
+            if let Ok(id) = attr_value.as_n() {
+                let mut is_duplicated = false;
+                let mut target_index = 0u32;
+                for m_data in &mountain_data_list {
+                    if m_data.index == *id {
+                        is_duplicated = true;
+                        break;
+                    }
+                    target_index += 1;
+                }
+
+                if !is_duplicated {
+                    mountain_data_list.push(MountainData {
+                        index: id.to_string(),
+                        attribute_data_list: vec![item],
+                    });
+                } else {
+                    mountain_data_list[target_index as usize]
+                        .attribute_data_list
+                        .push(item);
+                }
             }
         }
-        Err(_) => Err(()),
     }
+
+    mountain_data_list
+        .into_iter()
+        .map(|mountain_data| mountain_data.attribute_data_list)
+        .collect()
 }
 
-pub async fn get_mountain_by_id(client: &Client, id: String) -> Result<Mountain, ()> {
+/// 詳細表示用に sub_peaks を設定した山岳を取得する
+pub async fn get_mountain_by_id(client: &Client, id: String) -> Result<Mountain, ServiceError> {
+    let mut mountain = find_mountain_by_id(client, id).await?;
+    attach_sub_peaks(client, std::slice::from_mut(&mut mountain)).await?;
+    Ok(mountain)
+}
+
+/// sub_peaks を設定せずに山岳を取得する
+pub(crate) async fn find_mountain_by_id(
+    client: &Client,
+    id: String,
+) -> Result<Mountain, ServiceError> {
+    // 数値でない山岳IDは query がエラーとなるため、存在しないものとする
+    if id.parse::<u32>().is_err() {
+        return Err(ServiceError::NotFound);
+    }
+
     let command = QueryCommand {
         table: "Mountains".to_string(),
         index: None,
@@ -114,21 +187,97 @@ pub async fn get_mountain_by_id(client: &Client, id: String) -> Result<Mountain,
 
     match query(client, command).await {
         Ok(response) => {
+            // 山岳が存在しない場合
+            if response.is_empty() {
+                return Err(ServiceError::NotFound);
+            }
+
             let mapper = MountainMapper::new(response);
-            Ok(mapper.to_mountain())
+            mapper.to_mountain().map_err(ServiceError::Internal)
+        }
+        Err(_) => Err(ServiceError::query_failed("Mountains")),
+    }
+}
+
+/// 取得済みの山岳に sub_peaks を設定する（山岳の数によらず query は1回）
+pub(crate) async fn attach_sub_peaks(
+    client: &Client,
+    mountains: &mut [Mountain],
+) -> Result<(), ServiceError> {
+    if mountains.is_empty() {
+        return Ok(());
+    }
+
+    let sub_peak_ids = find_sub_peak_ids(client).await?;
+    for mountain in mountains.iter_mut() {
+        mountain.sub_peaks = sub_peak_ids.get(&mountain.id).cloned().unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+/// Parent 項目を DataType_Id_Index でまとめて取得し、親の山岳ID毎に子の山岳IDを取得する
+async fn find_sub_peak_ids(client: &Client) -> Result<HashMap<u32, Vec<u32>>, ServiceError> {
+    let command = QueryCommand {
+        table: "Mountains".to_string(),
+        index: Some("DataType_Id_Index".to_string()),
+        key: "DataType".to_string(),
+        value: "Parent".to_string(),
+    };
+
+    match query_index(client, command).await {
+        Ok(response) => Ok(group_sub_peak_ids(&response)),
+        Err(_) => Err(ServiceError::query_failed("Mountains")),
+    }
+}
+
+fn group_sub_peak_ids(items: &[HashMap<String, AttributeValue>]) -> HashMap<u32, Vec<u32>> {
+    let id_key = String::from("Id");
+    let value_key = String::from("DataValue");
+
+    let mut sub_peak_ids: HashMap<u32, Vec<u32>> = HashMap::new();
+    for item in items {
+        let id = get_value(item, &id_key, ValueType::Number).parse::<u32>();
+        let parent_id = get_value(item, &value_key, ValueType::String)
+            .replace("Parent_", "")
+            .parse::<u32>();
+        if let (Ok(id), Ok(parent_id)) = (id, parent_id) {
+            sub_peak_ids.entry(parent_id).or_default().push(id);
         }
-        Err(_) => Err(()),
+    }
+    for ids in sub_peak_ids.values_mut() {
+        ids.sort_unstable();
+        ids.dedup();
+    }
+
+    sub_peak_ids
+}
+
+/// 各山岳の parent から親の sub_peaks を導出する
+fn link_sub_peaks(mountains: &mut [Mountain]) {
+    let mut sub_peak_ids: HashMap<u32, Vec<u32>> = HashMap::new();
+    for mountain in mountains.iter() {
+        if let Some(parent_id) = mountain.parent {
+            sub_peak_ids.entry(parent_id).or_default().push(mountain.id);
+        }
+    }
+
+    for mountain in mountains.iter_mut() {
+        let mut ids = sub_peak_ids.remove(&mountain.id).unwrap_or_default();
+        ids.sort_unstable();
+        mountain.sub_peaks = ids;
     }
 }
 
 pub async fn search_mountains(
     client: &Client,
     search_conditions: Vec<SearchCondition>,
+    filter_condition: FilterCondition,
     range_condition: RangeCondition,
-    sort_key: &String,
-) -> Result<SearchedMountainResult, ()> {
-    // 検索結果を格納する
-    let mut searched_list: Vec<String> = Vec::new();
+    sort_key: &str,
+) -> Result<SearchedMountainResult, ServiceError> {
+    // 検索結果を格納する（None は条件を未適用）
+    let mut searched_list: Option<Vec<String>> = None;
 
     for condition in search_conditions {
         let command = QueryCommand {
@@ -156,96 +305,207 @@ pub async fn search_mountains(
             filter_value: condition.value.to_string(),
         };
 
+        let alias_command = QueryCommand {
+            table: "Mountains".to_string(),
+            index: Some("DataType_Id_Index".to_string()),
+            key: "DataType".to_string(),
+            value: "Alias".to_string(),
+        };
+
         let key = String::from("Id");
+        // 取得に失敗した条件を無視すると結果が広がるため、エラーとする
+        let query_failed = |_| ServiceError::query_failed("Mountains");
 
         match condition.search_type {
-            SearchType::Prefecture | SearchType::Tag => match query_index(client, command).await {
-                Ok(response) => {
-                    let mut temp_result: Vec<String> = Vec::new();
-                    for item in response {
-                        let id = get_value(&item, &key, ValueType::Number);
-                        temp_result.push(id);
-                    }
-
-                    merge_result(&mut searched_list, &temp_result);
+            SearchType::Prefecture
+            | SearchType::Tag
+            | SearchType::MountainRange
+            | SearchType::Park => {
+                let response = query_index(client, command).await.map_err(query_failed)?;
+                let mut temp_result: Vec<String> = Vec::new();
+                for item in response {
+                    let id = get_value(&item, &key, ValueType::Number);
+                    temp_result.push(id);
                 }
-                Err(_) => {}
-            },
+
+                merge_result(&mut searched_list, temp_result);
+            }
             SearchType::Name => {
                 let mut temp_name_result: Vec<String> = Vec::new();
 
-                match query_index_filter(client, filter_command).await {
-                    Ok(response) => {
-                        for item in response {
-                            let id = get_value(&item, &key, ValueType::Number);
-                            temp_name_result.push(id);
-                        }
+                for filter_command in [filter_command, filter_kana_command] {
+                    let response = query_index_filter(client, filter_command)
+                        .await
+                        .map_err(query_failed)?;
+                    for item in response {
+                        let id = get_value(&item, &key, ValueType::Number);
+                        temp_name_result.push(id);
                     }
-                    Err(_) => {}
                 }
-                match query_index_filter(client, filter_kana_command).await {
-                    Ok(response) => {
-                        for item in response {
-                            let id = get_value(&item, &key, ValueType::Number);
-                            temp_name_result.push(id);
-                        }
+
+                // 別名は AliasValues のリストのため、取得後に別名毎に照合する
+                let response = query_index(client, alias_command)
+                    .await
+                    .map_err(query_failed)?;
+                for item in response {
+                    if alias_matches(&get_aliases(&item), &condition.value) {
+                        let id = get_value(&item, &key, ValueType::Number);
+                        temp_name_result.push(id);
                     }
-                    Err(_) => {}
                 }
 
                 temp_name_result.sort();
                 temp_name_result.dedup();
-                merge_result(&mut searched_list, &temp_name_result);
+                merge_result(&mut searched_list, temp_name_result);
             }
         }
     }
 
     let mut mountains: Vec<Mountain> = Vec::new();
-    for id in searched_list {
-        match get_mountain_by_id(client, id).await {
-            Ok(mountain) => {
-                mountains.push(mountain);
-            }
-            Err(_) => {}
+    for id in searched_list.unwrap_or_default() {
+        match find_mountain_by_id(client, id).await {
+            Ok(mountain) => mountains.push(mountain),
+            // 検索後に削除された山岳は除外する
+            Err(ServiceError::NotFound) => {}
+            Err(error) => return Err(error),
         }
     }
 
+    filter_mountains(&mut mountains, &filter_condition);
+    attach_sub_peaks(client, &mut mountains).await?;
+
     // sorting
-    if mountains.len() > 0 {
+    if !mountains.is_empty() {
         sort_mountains(&mut mountains, sort_key);
     }
 
     // offset, limitによる絞り込み
-    match refine_mountains(&mountains, range_condition) {
-        Ok(refined_mountain_result) => Ok(SearchedMountainResult {
-            mountains: refined_mountain_result.mountains,
-            total: refined_mountain_result.total,
-            offset: refined_mountain_result.offset,
-            limit: refined_mountain_result.limit,
-        }),
-        Err(_) => Err(()),
+    let refined_mountain_result = refine_mountains(&mountains, range_condition)?;
+    Ok(SearchedMountainResult {
+        mountains: refined_mountain_result.mountains,
+        total: refined_mountain_result.total,
+        offset: refined_mountain_result.offset,
+        limit: refined_mountain_result.limit,
+    })
+}
+
+pub async fn get_nearby_mountains(
+    client: &Client,
+    id: String,
+    nearby_condition: NearbyCondition,
+) -> Result<NearbyMountainResult, ServiceError> {
+    let origin = find_mountain_by_id(client, id).await?;
+    let mountains = scan_mountains(client).await?;
+
+    let mut nearby_mountains: Vec<NearbyMountain> = Vec::new();
+    for mountain in mountains {
+        // 起点の山岳は除外する
+        if mountain.id == origin.id {
+            continue;
+        }
+
+        let distance = origin.location.distance_to(&mountain.location);
+        if distance <= nearby_condition.radius {
+            let bearing = origin.location.bearing_to(&mountain.location);
+            nearby_mountains.push(NearbyMountain {
+                mountain,
+                distance,
+                bearing,
+                direction: to_direction(bearing),
+            });
+        }
     }
+
+    // 距離の近い順
+    nearby_mountains.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(Ordering::Equal)
+            .then(a.mountain.id.cmp(&b.mountain.id))
+    });
+
+    let total = nearby_mountains.len();
+    nearby_mountains.truncate(nearby_condition.limit);
+
+    Ok(NearbyMountainResult {
+        mountains: nearby_mountains,
+        total,
+        radius: nearby_condition.radius,
+        limit: nearby_condition.limit,
+    })
 }
 
-fn merge_result(base_list: &mut Vec<String>, target_list: &Vec<String>) {
-    if base_list.len() > 0 {
-        let mut keep: Vec<bool> = Vec::new();
-        for base_id in base_list.into_iter() {
-            let mut is_duplicated = false;
-            for target_id in target_list {
-                if base_id == target_id {
-                    is_duplicated = true;
-                }
+pub async fn get_sub_peaks(client: &Client, id: String) -> Result<Vec<Mountain>, ServiceError> {
+    let mountain = find_mountain_by_id(client, id).await?;
+    let sub_peak_ids = find_sub_peak_ids(client).await?;
+
+    let mut sub_peaks: Vec<Mountain> = Vec::new();
+    for sub_peak_id in sub_peak_ids.get(&mountain.id).cloned().unwrap_or_default() {
+        match find_mountain_by_id(client, sub_peak_id.to_string()).await {
+            Ok(mut sub_peak) => {
+                sub_peak.sub_peaks = sub_peak_ids.get(&sub_peak.id).cloned().unwrap_or_default();
+                sub_peaks.push(sub_peak);
             }
-            keep.push(is_duplicated);
+            Err(ServiceError::NotFound) => {}
+            Err(error) => return Err(error),
         }
+    }
+    sort_mountains(&mut sub_peaks, "id.asc");
 
-        let mut iter = keep.iter();
-        base_list.retain(|_| *iter.next().unwrap());
-    } else {
-        for target_id in target_list {
-            base_list.push(target_id.to_string());
-        }
+    Ok(sub_peaks)
+}
+
+fn filter_mountains(mountains: &mut Vec<Mountain>, filter_condition: &FilterCondition) {
+    if filter_condition.top_level_only {
+        mountains.retain(|mountain| mountain.parent.is_none());
+    }
+
+    if let Some(primary_prefecture) = &filter_condition.primary_prefecture {
+        mountains
+            .retain(|mountain| mountain.primary_prefecture.as_ref() == Some(primary_prefecture));
+    }
+
+    if let Some(volcano) = filter_condition.volcano {
+        mountains.retain(|mountain| is_active_volcano(mountain) == volcano);
+    }
+
+    if let Some(month) = filter_condition.in_season {
+        mountains.retain(|mountain| match &mountain.season {
+            Some(season) => season.months.contains(&month),
+            None => false,
+        });
+    }
+
+    // いずれかのルートが技術的難易度、体力度（指定した場合）ともに上限以下の山岳
+    if let Some(difficulty_max) = filter_condition.difficulty_max {
+        mountains.retain(|mountain| {
+            mountain
+                .route_difficulties
+                .iter()
+                .any(|difficulty| difficulty_max.allows(difficulty))
+        });
+    }
+
+    if let Some(alert_level_min) = filter_condition.alert_level_min {
+        mountains.retain(|mountain| match &mountain.volcano {
+            Some(v) => v.alert_level.unwrap_or(0) >= alert_level_min,
+            None => false,
+        });
+    }
+}
+
+fn is_active_volcano(mountain: &Mountain) -> bool {
+    match &mountain.volcano {
+        Some(v) => v.active,
+        None => false,
+    }
+}
+
+/// 条件毎の検索結果の積集合をとる（最初の条件の結果はそのまま採用する）
+fn merge_result(base_list: &mut Option<Vec<String>>, target_list: Vec<String>) {
+    match base_list {
+        Some(base_list) => base_list.retain(|base_id| target_list.contains(base_id)),
+        None => *base_list = Some(target_list),
     }
 }
 
@@ -257,22 +517,19 @@ struct RefinedMountainResult {
 }
 
 fn refine_mountains(
-    mountains: &Vec<Mountain>,
+    mountains: &[Mountain],
     range_condition: RangeCondition,
-) -> Result<RefinedMountainResult, String> {
+) -> Result<RefinedMountainResult, ServiceError> {
     let range_from = range_condition.offset;
-    let mut range_to = mountains.len() as usize;
-    match range_condition.limit {
-        Some(range_condition_limit) => {
-            if range_to > range_condition_limit + range_from {
-                range_to = range_condition_limit + range_from;
-            }
+    let mut range_to = mountains.len();
+    if let Some(range_condition_limit) = range_condition.limit {
+        if range_to > range_condition_limit + range_from {
+            range_to = range_condition_limit + range_from;
         }
-        None => {}
     }
 
     if range_from > range_to {
-        return Err("offsetの値が不正です。".to_string());
+        return Err(ServiceError::OffsetOutOfRange);
     }
 
     Ok(RefinedMountainResult {
@@ -283,19 +540,37 @@ fn refine_mountains(
     })
 }
 
-fn sort_mountains(mountains: &mut Vec<Mountain>, sort_key: &String) {
-    match sort_key.as_str() {
+fn sort_mountains(mountains: &mut [Mountain], sort_key: &str) {
+    match sort_key {
         "id.asc" => {
-            mountains.sort_by(|a, b| a.id.cmp(&b.id));
+            mountains.sort_by_key(|a| a.id);
         }
         "id.desc" => {
-            mountains.sort_by(|a, b| b.id.cmp(&a.id));
+            mountains.sort_by_key(|a| Reverse(a.id));
         }
         "elevation.asc" => {
-            mountains.sort_by(|a, b| a.elevation.cmp(&b.elevation));
+            mountains.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
         }
         "elevation.desc" => {
-            mountains.sort_by(|a, b| b.elevation.cmp(&a.elevation));
+            mountains.sort_by(|a, b| b.elevation.total_cmp(&a.elevation));
+        }
+        "difficulty.asc" => {
+            // 最も易しいルートの難易度で比較し、難易度が未設定の山岳は末尾にする
+            mountains.sort_by(|a, b| match (&a.difficulty, &b.difficulty) {
+                (Some(a_difficulty), Some(b_difficulty)) => a_difficulty.min.cmp(&b_difficulty.min),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        }
+        "difficulty.desc" => {
+            // 最も難しいルートの難易度で比較する
+            mountains.sort_by(|a, b| match (&a.difficulty, &b.difficulty) {
+                (Some(a_difficulty), Some(b_difficulty)) => b_difficulty.max.cmp(&a_difficulty.max),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
         }
         "name.asc" => {
             mountains.sort_by(|a, b| a.name_kana.cmp(&b.name_kana));
@@ -304,7 +579,246 @@ fn sort_mountains(mountains: &mut Vec<Mountain>, sort_key: &String) {
             mountains.sort_by(|a, b| b.name_kana.cmp(&a.name_kana));
         }
         _ => {
-            mountains.sort_by(|a, b| a.id.cmp(&b.id));
+            mountains.sort_by_key(|a| a.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Difficulty, DifficultySummary, PhysicalGrade, TechnicalGrade, Volcano};
+
+    fn difficulty(technical: TechnicalGrade, physical: u8) -> Difficulty {
+        Difficulty {
+            technical,
+            physical: PhysicalGrade::try_from(physical).unwrap(),
+        }
+    }
+
+    fn mountain_with_routes(id: u32, route_difficulties: Vec<Difficulty>) -> Mountain {
+        Mountain {
+            id,
+            difficulty: DifficultySummary::from_difficulties(&route_difficulties),
+            route_difficulties,
+            ..Default::default()
         }
     }
+
+    fn graded_mountains() -> Vec<Mountain> {
+        vec![
+            // 易しいルートと非常に難しいルートを持つ
+            mountain_with_routes(
+                1,
+                vec![
+                    difficulty(TechnicalGrade::A, 2),
+                    difficulty(TechnicalGrade::E, 9),
+                ],
+            ),
+            mountain_with_routes(2, vec![difficulty(TechnicalGrade::C, 5)]),
+            mountain_with_routes(3, vec![]),
+            mountain_with_routes(
+                4,
+                vec![
+                    difficulty(TechnicalGrade::B, 7),
+                    difficulty(TechnicalGrade::C, 3),
+                ],
+            ),
+        ]
+    }
+
+    fn ids(mountains: &[Mountain]) -> Vec<u32> {
+        mountains.iter().map(|m| m.id).collect()
+    }
+
+    #[test]
+    fn sort_by_difficulty() {
+        let mut mountains = graded_mountains();
+        sort_mountains(&mut mountains, "difficulty.asc");
+        assert_eq!(ids(&mountains), vec![1, 4, 2, 3]);
+
+        // 降順は最も難しいルートで比較し、難易度のない山岳は末尾にする
+        sort_mountains(&mut mountains, "difficulty.desc");
+        assert_eq!(ids(&mountains), vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn filter_by_difficulty_max() {
+        let filter = |limit: &str| {
+            let mut mountains = graded_mountains();
+            filter_mountains(
+                &mut mountains,
+                &FilterCondition {
+                    difficulty_max: Some(DifficultyLimit::try_from(limit).unwrap()),
+                    ..Default::default()
+                },
+            );
+            ids(&mountains)
+        };
+
+        assert_eq!(filter("A"), vec![1]);
+        assert_eq!(filter("B"), vec![1, 4]);
+        assert_eq!(filter("C"), vec![1, 2, 4]);
+        // 体力度も上限以下のルートが必要
+        assert_eq!(filter("C4"), vec![1, 4]);
+        assert_eq!(filter("B6"), vec![1]);
+        assert_eq!(filter("E1"), Vec::<u32>::new());
+    }
+
+    fn volcano_mountain(id: u32, volcano: Option<(bool, Option<u8>)>) -> Mountain {
+        Mountain {
+            id,
+            volcano: volcano.map(|(active, alert_level)| Volcano {
+                active,
+                monitored: active,
+                alert_level,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn filter_volcanoes(volcano: Option<bool>, alert_level_min: Option<u8>) -> Vec<u32> {
+        let mut mountains = vec![
+            volcano_mountain(1, Some((true, Some(3)))),
+            volcano_mountain(2, Some((true, None))),
+            volcano_mountain(3, Some((false, None))),
+            volcano_mountain(4, None),
+            volcano_mountain(5, Some((true, Some(1)))),
+        ];
+        filter_mountains(
+            &mut mountains,
+            &FilterCondition {
+                volcano,
+                alert_level_min,
+                ..Default::default()
+            },
+        );
+        ids(&mountains)
+    }
+
+    #[test]
+    fn filter_by_volcano() {
+        assert_eq!(filter_volcanoes(None, None), vec![1, 2, 3, 4, 5]);
+        assert_eq!(filter_volcanoes(Some(true), None), vec![1, 2, 5]);
+        // 火山情報がない山岳は活火山でないものとする
+        assert_eq!(filter_volcanoes(Some(false), None), vec![3, 4]);
+    }
+
+    #[test]
+    fn filter_by_alert_level_min() {
+        // 噴火警戒レベルが未導入の火山は含めない
+        assert_eq!(filter_volcanoes(None, Some(1)), vec![1, 5]);
+        assert_eq!(filter_volcanoes(None, Some(2)), vec![1]);
+        assert_eq!(filter_volcanoes(None, Some(4)), Vec::<u32>::new());
+        assert_eq!(filter_volcanoes(Some(false), Some(1)), Vec::<u32>::new());
+    }
+
+    fn mountain(id: u32, parent: Option<u32>) -> Mountain {
+        Mountain {
+            id,
+            parent,
+            ..Default::default()
+        }
+    }
+
+    fn parent_item(id: &str, data_value: &str) -> HashMap<String, AttributeValue> {
+        let mut item: HashMap<String, AttributeValue> = HashMap::new();
+        item.insert("Id".to_string(), AttributeValue::N(id.to_string()));
+        item.insert(
+            "DataType".to_string(),
+            AttributeValue::S("Parent".to_string()),
+        );
+        item.insert(
+            "DataValue".to_string(),
+            AttributeValue::S(data_value.to_string()),
+        );
+        item
+    }
+
+    #[test]
+    fn sub_peak_ids_are_grouped_by_parent() {
+        let sub_peak_ids = group_sub_peak_ids(&[
+            parent_item("3", "Parent_1"),
+            parent_item("2", "Parent_1"),
+            parent_item("2", "Parent_1"),
+            parent_item("5", "Parent_4"),
+            parent_item("6", "Parent_x"),
+        ]);
+
+        assert_eq!(sub_peak_ids.len(), 2);
+        assert_eq!(sub_peak_ids[&1], vec![2, 3]);
+        assert_eq!(sub_peak_ids[&4], vec![5]);
+    }
+
+    fn id_list(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn merge_result_intersects_conditions() {
+        let mut searched_list: Option<Vec<String>> = None;
+        merge_result(&mut searched_list, id_list(&["1", "2", "3"]));
+        merge_result(&mut searched_list, id_list(&["3", "2", "9"]));
+        assert_eq!(searched_list, Some(id_list(&["2", "3"])));
+    }
+
+    #[test]
+    fn merge_result_keeps_empty_first_condition() {
+        // 最初の条件が0件の場合、後続の条件で結果が広がらない
+        let mut searched_list: Option<Vec<String>> = None;
+        merge_result(&mut searched_list, Vec::new());
+        merge_result(&mut searched_list, id_list(&["1", "2"]));
+        assert_eq!(searched_list, Some(Vec::new()));
+
+        // 途中で0件になった場合も同様
+        let mut searched_list: Option<Vec<String>> = None;
+        merge_result(&mut searched_list, id_list(&["1"]));
+        merge_result(&mut searched_list, id_list(&["2"]));
+        merge_result(&mut searched_list, id_list(&["2"]));
+        assert_eq!(searched_list, Some(Vec::new()));
+    }
+
+    #[test]
+    fn refine_by_offset_and_limit() {
+        let mountains: Vec<Mountain> = (1..=5).map(|id| mountain(id, None)).collect();
+        let refine = |offset: usize, limit: Option<usize>| {
+            refine_mountains(&mountains, RangeCondition { offset, limit })
+                .map(|result| (ids(&result.mountains), result.total))
+        };
+
+        assert_eq!(refine(0, None), Ok((vec![1, 2, 3, 4, 5], 5)));
+        assert_eq!(refine(1, Some(2)), Ok((vec![2, 3], 5)));
+        assert_eq!(refine(4, Some(10)), Ok((vec![5], 5)));
+        // offset が件数と等しい場合は空とする
+        assert_eq!(refine(5, Some(10)), Ok((vec![], 5)));
+        // 件数を超える offset は内部エラーと区別する
+        assert_eq!(refine(6, None), Err(ServiceError::OffsetOutOfRange));
+    }
+
+    #[test]
+    fn sub_peaks_are_derived_from_parent() {
+        let mut mountains = vec![
+            mountain(3, Some(1)),
+            mountain(1, None),
+            mountain(2, Some(1)),
+            mountain(4, Some(9)),
+            mountain(5, None),
+        ];
+        link_sub_peaks(&mut mountains);
+
+        let sub_peaks: Vec<(u32, Vec<u32>)> = mountains
+            .iter()
+            .map(|m| (m.id, m.sub_peaks.clone()))
+            .collect();
+        assert_eq!(
+            sub_peaks,
+            vec![
+                (3, vec![]),
+                (1, vec![2, 3]),
+                (2, vec![]),
+                (4, vec![]),
+                (5, vec![]),
+            ]
+        );
+    }
 }
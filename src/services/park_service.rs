@@ -0,0 +1,67 @@
+use crate::models::{Mountain, Park, ParkMapper};
+use crate::services::{scan_mountains, ServiceError};
+use aws_sdk_dynamodb::Client;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// 国立公園と、その区域内にある山岳の数
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ParkCount {
+    #[serde(flatten)]
+    pub park: Park,
+    pub count: usize,
+}
+
+pub async fn get_park_counts(client: &Client) -> Result<Vec<ParkCount>, ServiceError> {
+    let mountains = scan_mountains(client).await?;
+    Ok(count_parks(&mountains))
+}
+
+fn count_parks(mountains: &[Mountain]) -> Vec<ParkCount> {
+    let mut park_counts: Vec<ParkCount> = Vec::new();
+    for park in ParkMapper::all() {
+        let count = mountains
+            .iter()
+            .filter(|mountain| mountain.parks.contains(&park.name))
+            .count();
+        park_counts.push(ParkCount { park, count });
+    }
+
+    park_counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mountain(id: u32, parks: &[&str]) -> Mountain {
+        Mountain {
+            id,
+            parks: parks.iter().map(|park| park.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn mountains_are_counted_per_park() {
+        let mountains = vec![
+            mountain(1, &["中部山岳国立公園"]),
+            mountain(2, &["中部山岳国立公園", "白山国立公園"]),
+            mountain(3, &[]),
+        ];
+        let park_counts = count_parks(&mountains);
+
+        // 山岳のない国立公園も 0 件として含める
+        assert_eq!(park_counts.len(), ParkMapper::all().len());
+        let count = |name: &str| {
+            park_counts
+                .iter()
+                .find(|park_count| park_count.park.name == name)
+                .map(|park_count| park_count.count)
+        };
+        assert_eq!(count("中部山岳国立公園"), Some(2));
+        assert_eq!(count("白山国立公園"), Some(1));
+        assert_eq!(count("知床国立公園"), Some(0));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::models::{GroupRank, Mountain, Rank, RankValue};
+use crate::services::{scan_mountains, ServiceError};
+use aws_sdk_dynamodb::Client;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 標高インデックスを再構築するまでの保持期間
+const ELEVATION_INDEX_TTL: Duration = Duration::from_secs(600);
+
+/// Lambdaのコンテナが再利用される間はインデックスを使い回す
+static ELEVATION_INDEX: Mutex<Option<CachedElevationIndex>> = Mutex::new(None);
+
+struct CachedElevationIndex {
+    built_at: Instant,
+    index: Arc<ElevationIndex>,
+}
+
+impl CachedElevationIndex {
+    /// 保持期間内であればインデックスを返す
+    fn fresh_index(&self, now: Instant) -> Option<Arc<ElevationIndex>> {
+        if now.saturating_duration_since(self.built_at) < ELEVATION_INDEX_TTL {
+            Some(Arc::clone(&self.index))
+        } else {
+            None
+        }
+    }
+}
+
+/// 全国・都道府県別・タグ別に標高を降順で保持するインデックス
+pub struct ElevationIndex {
+    national: Vec<f64>,
+    by_prefecture: HashMap<String, Vec<f64>>,
+    by_tag: HashMap<String, Vec<f64>>,
+}
+
+impl ElevationIndex {
+    pub fn new(mountains: &[Mountain]) -> Self {
+        let mut national: Vec<f64> = Vec::new();
+        let mut by_prefecture: HashMap<String, Vec<f64>> = HashMap::new();
+        let mut by_tag: HashMap<String, Vec<f64>> = HashMap::new();
+
+        for mountain in mountains {
+            national.push(mountain.elevation);
+            for prefecture in &mountain.prefectures {
+                by_prefecture
+                    .entry(prefecture.to_string())
+                    .or_default()
+                    .push(mountain.elevation);
+            }
+            for tag in &mountain.tags {
+                by_tag
+                    .entry(tag.to_string())
+                    .or_default()
+                    .push(mountain.elevation);
+            }
+        }
+
+        sort_desc(&mut national);
+        by_prefecture.values_mut().for_each(|e| sort_desc(e));
+        by_tag.values_mut().for_each(|e| sort_desc(e));
+
+        Self {
+            national,
+            by_prefecture,
+            by_tag,
+        }
+    }
+
+    pub fn rank(&self, mountain: &Mountain) -> Rank {
+        Rank {
+            national: RankValue {
+                rank: rank_in(&self.national, mountain.elevation),
+                total: self.national.len(),
+            },
+            by_prefecture: group_ranks(
+                &self.by_prefecture,
+                &mountain.prefectures,
+                mountain.elevation,
+            ),
+            by_tag: group_ranks(&self.by_tag, &mountain.tags, mountain.elevation),
+        }
+    }
+}
+
+/// 山岳は取得済みのため、インデックスを構築できない場合は内部エラーとする
+pub async fn get_mountain_rank(client: &Client, mountain: &Mountain) -> Result<Rank, ServiceError> {
+    let index = elevation_index(client).await.map_err(|error| match error {
+        ServiceError::Internal(cause) => ServiceError::Internal(cause),
+        _ => ServiceError::Internal("標高インデックスを構築できません。".to_string()),
+    })?;
+    Ok(index.rank(mountain))
+}
+
+async fn elevation_index(client: &Client) -> Result<Arc<ElevationIndex>, ServiceError> {
+    if let Ok(cache) = ELEVATION_INDEX.lock() {
+        if let Some(index) = cache
+            .as_ref()
+            .and_then(|cached| cached.fresh_index(Instant::now()))
+        {
+            return Ok(index);
+        }
+    }
+
+    let mountains = scan_mountains(client).await?;
+    let index = Arc::new(ElevationIndex::new(&mountains));
+
+    if let Ok(mut cache) = ELEVATION_INDEX.lock() {
+        *cache = Some(CachedElevationIndex {
+            built_at: Instant::now(),
+            index: Arc::clone(&index),
+        });
+    }
+
+    Ok(index)
+}
+
+fn sort_desc(elevations: &mut [f64]) {
+    elevations.sort_unstable_by(|a, b| b.total_cmp(a));
+}
+
+/// 自身より標高の高い山岳の数 + 1 を順位とする
+fn rank_in(elevations: &[f64], elevation: f64) -> usize {
+    elevations.partition_point(|e| *e > elevation) + 1
+}
+
+fn group_ranks(
+    groups: &HashMap<String, Vec<f64>>,
+    names: &[String],
+    elevation: f64,
+) -> Vec<GroupRank> {
+    let mut ranks: Vec<GroupRank> = Vec::new();
+    for name in names {
+        if let Some(elevations) = groups.get(name) {
+            ranks.push(GroupRank {
+                name: name.to_string(),
+                rank: rank_in(elevations, elevation),
+                total: elevations.len(),
+            });
+        }
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mountain(id: u32, elevation: f64, prefectures: &[&str], tags: &[&str]) -> Mountain {
+        Mountain {
+            id,
+            elevation,
+            prefectures: prefectures.iter().map(|p| p.to_string()).collect(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn mountains() -> Vec<Mountain> {
+        vec![
+            mountain(1, 3776.0, &["静岡県", "山梨県"], &["百名山"]),
+            mountain(2, 3190.0, &["長野県", "岐阜県"], &["百名山"]),
+            mountain(3, 3193.0, &["山梨県"], &["百名山"]),
+            mountain(4, 3190.0, &["長野県"], &["二百名山"]),
+            mountain(5, 3180.0, &["長野県", "富山県"], &["百名山"]),
+        ]
+    }
+
+    #[test]
+    fn tied_elevations_share_rank_and_skip_next() {
+        let mountains = mountains();
+        let index = ElevationIndex::new(&mountains);
+
+        let ranks: Vec<usize> = mountains
+            .iter()
+            .map(|m| index.rank(m).national.rank)
+            .collect();
+        // 3190m の2山は同順位3位、次の 3180m は5位
+        assert_eq!(ranks, vec![1, 3, 2, 3, 5]);
+        assert!(mountains.iter().all(|m| index.rank(m).national.total == 5));
+    }
+
+    #[test]
+    fn group_ranks_follow_mountain_order() {
+        let mountains = mountains();
+        let index = ElevationIndex::new(&mountains);
+
+        let rank = index.rank(&mountains[4]);
+        assert_eq!(
+            rank.by_prefecture,
+            vec![
+                GroupRank {
+                    name: "長野県".to_string(),
+                    rank: 3,
+                    total: 3,
+                },
+                GroupRank {
+                    name: "富山県".to_string(),
+                    rank: 1,
+                    total: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            rank.by_tag,
+            vec![GroupRank {
+                name: "百名山".to_string(),
+                rank: 4,
+                total: 4,
+            }]
+        );
+
+        // 同じ標高の山岳はグループ内でも同順位
+        assert_eq!(index.rank(&mountains[1]).by_prefecture[0].rank, 1);
+        assert_eq!(index.rank(&mountains[3]).by_prefecture[0].rank, 1);
+    }
+
+    #[test]
+    fn rank_of_mountain_outside_index() {
+        let index = ElevationIndex::new(&mountains());
+
+        let rank = index.rank(&mountain(9, 4000.0, &["北海道"], &[]));
+        assert_eq!(rank.national.rank, 1);
+        assert!(rank.by_prefecture.is_empty());
+        assert!(rank.by_tag.is_empty());
+
+        let rank = index.rank(&mountain(9, 100.0, &[], &[]));
+        assert_eq!(rank.national.rank, 6);
+    }
+
+    #[test]
+    fn cached_index_expires_after_ttl() {
+        let built_at = Instant::now();
+        let cached = CachedElevationIndex {
+            built_at,
+            index: Arc::new(ElevationIndex::new(&mountains())),
+        };
+
+        assert!(cached.fresh_index(built_at).is_some());
+        assert!(cached
+            .fresh_index(built_at + ELEVATION_INDEX_TTL - Duration::from_secs(1))
+            .is_some());
+        assert!(cached.fresh_index(built_at + ELEVATION_INDEX_TTL).is_none());
+    }
+}
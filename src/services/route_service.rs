@@ -0,0 +1,136 @@
+use crate::models::{
+    get_track, simplify_track, to_line_string, track_statistics, Feature, Route, RouteBaseMapper,
+    RouteMapper, TrackPoint, TrackStatistics, ROUTE_TRACK_TABLE,
+};
+use crate::repositories::{query, query_index, QueryCommand};
+use crate::services::ServiceError;
+use aws_sdk_dynamodb::Client;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackProperties {
+    pub route_id: u32,
+    pub mountain_id: u32,
+    pub name: String,
+    #[serde(flatten)]
+    pub statistics: TrackStatistics,
+    /// 元の座標数
+    pub point_count: usize,
+    /// 間引き後の座標数
+    pub simplified_point_count: usize,
+}
+
+pub async fn get_routes_by_mountain_id(
+    client: &Client,
+    id: String,
+) -> Result<Vec<Route>, ServiceError> {
+    // 数値でない山岳IDは query がエラーとなるため、存在しないものとする
+    if id.parse::<u32>().is_err() {
+        return Err(ServiceError::NotFound);
+    }
+
+    let command = QueryCommand {
+        table: "Mountains".to_string(),
+        index: None,
+        key: "Id".to_string(),
+        value: id,
+    };
+
+    match query(client, command).await {
+        // 山岳が存在しない場合
+        Ok(response) if response.is_empty() => Err(ServiceError::NotFound),
+        Ok(response) => {
+            let mut routes: Vec<Route> = Vec::new();
+            for item in response {
+                if let Some(type_attr) = item.get("DataType") {
+                    if let Ok(data_type) = type_attr.as_s() {
+                        if Some(0) == data_type.find("Route_") {
+                            let mapper = RouteMapper::new(item);
+                            routes.push(mapper.to_route());
+                        }
+                    }
+                }
+            }
+            routes.sort_by_key(|route| route.id);
+
+            Ok(routes)
+        }
+        Err(_) => Err(ServiceError::query_failed("Mountains")),
+    }
+}
+
+pub async fn get_route_by_id(client: &Client, id: String) -> Result<Route, ServiceError> {
+    let command = QueryCommand {
+        table: "Mountains".to_string(),
+        index: Some("DataValue_Id_Index".to_string()),
+        key: "DataValue".to_string(),
+        value: format!("Route_{}", id),
+    };
+
+    match query_index(client, command).await {
+        Ok(response) => match response.into_iter().next() {
+            Some(item) => {
+                let mapper = RouteMapper::new(item);
+                Ok(mapper.to_route())
+            }
+            None => Err(ServiceError::NotFound),
+        },
+        Err(_) => Err(ServiceError::query_failed("Mountains")),
+    }
+}
+
+/// ルートの軌跡を GeoJSON の LineString として取得する（simplify は m）
+///
+/// 軌跡が2点未満の場合は None を返す。
+pub async fn get_route_track(
+    client: &Client,
+    id: String,
+    simplify: Option<f64>,
+) -> Result<Option<Feature<TrackProperties>>, ServiceError> {
+    let route = get_route_by_id(client, id).await?;
+    let points = get_track_points(client, route.id).await?;
+
+    // 統計値は間引く前の軌跡から算出する
+    let statistics = track_statistics(&points);
+    let track = match simplify {
+        Some(tolerance) => simplify_track(&points, tolerance),
+        None => points.clone(),
+    };
+
+    let line_string = match to_line_string(&track) {
+        Some(line_string) => line_string,
+        None => return Ok(None),
+    };
+
+    Ok(Some(Feature::new(
+        line_string,
+        TrackProperties {
+            route_id: route.id,
+            mountain_id: route.mountain_id,
+            name: route.name,
+            statistics,
+            point_count: points.len(),
+            simplified_point_count: track.len(),
+        },
+    )))
+}
+
+/// RouteTracks テーブルから軌跡を取得する（項目がない場合は空）
+async fn get_track_points(client: &Client, route_id: u32) -> Result<Vec<TrackPoint>, ServiceError> {
+    let command = QueryCommand {
+        table: ROUTE_TRACK_TABLE.to_string(),
+        index: None,
+        key: "Id".to_string(),
+        value: route_id.to_string(),
+    };
+
+    let items = query(client, command)
+        .await
+        .map_err(|_| ServiceError::query_failed(ROUTE_TRACK_TABLE))?;
+    Ok(items
+        .first()
+        .map(|item| get_track(item, "Track"))
+        .unwrap_or_default())
+}
@@ -0,0 +1,16 @@
+/// サービス層のエラー
+#[derive(Debug, PartialEq, Clone)]
+pub enum ServiceError {
+    /// 指定したリソースが存在しない
+    NotFound,
+    /// offset が検索結果の件数を超えている
+    OffsetOutOfRange,
+    /// テーブルの取得やデータの変換に失敗した（原因を保持する）
+    Internal(String),
+}
+
+impl ServiceError {
+    pub(crate) fn query_failed(table: &str) -> Self {
+        ServiceError::Internal(format!("{} テーブルの取得に失敗しました。", table))
+    }
+}